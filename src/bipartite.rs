@@ -0,0 +1,172 @@
+//! Bipartiteness and parity constraint checking
+
+use crate::partition::identifier;
+use crate::potential::{Identifier, PotentialVertex};
+use crate::{Potential, UnionFind, VertexType};
+
+/// Edge or constraint that closed an odd cycle, see [`Bipartite`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OddCycle<I> {
+    pub a: I,
+    pub b: I,
+}
+
+/// Incremental 2-coloring of `N` nodes
+///
+/// Every node stores its side relative to the root of its component as a `bool` potential of
+/// [`Potential`], so adding an edge or a parity constraint is a single
+/// [`UnionFind::union_with`] in `Θ(α(N))`. Edges that close an odd cycle are rejected and
+/// leave the coloring untouched, the first of them is remembered
+pub struct Bipartite<I, const N: usize>
+where
+    I: Identifier + 'static,
+{
+    uf: UnionFind<'static, Potential, PotentialVertex<I, bool>, N>,
+    odd_cycle: Option<OddCycle<I>>,
+}
+
+impl<I, const N: usize> Default for Bipartite<I, N>
+where
+    I: Identifier + 'static,
+    UnionFind<'static, Potential, PotentialVertex<I, bool>, N>: Default,
+{
+    fn default() -> Self {
+        Self {
+            uf: UnionFind::<'static, Potential, PotentialVertex<I, bool>, N>::default(),
+            odd_cycle: None,
+        }
+    }
+}
+
+impl<I, const N: usize> Bipartite<I, N>
+where
+    I: Identifier + 'static,
+{
+    /// Adds an edge, i.e. `a` and `b` must be on different sides
+    ///
+    /// # Errors
+    /// Returns [`OddCycle`] if `a` and `b` are already on the same side, the edge is not added
+    pub fn add_edge(&mut self, a: I, b: I) -> Result<(), OddCycle<I>> {
+        self.add_constraint(a, b, true)
+    }
+
+    /// Adds the constraint `side(a) ^ side(b) == parity`
+    ///
+    /// # Errors
+    /// Returns [`OddCycle`] if the constraint contradicts the earlier ones, it is not added
+    pub fn add_constraint(&mut self, a: I, b: I, parity: bool) -> Result<(), OddCycle<I>> {
+        match self.uf.union_with(a, b, parity) {
+            Ok(_) => Ok(()),
+            Err(_) => {
+                let odd_cycle = OddCycle { a, b };
+                self.odd_cycle.get_or_insert(odd_cycle);
+                Err(odd_cycle)
+            }
+        }
+    }
+
+    /// Whether every edge added so far was accepted
+    pub fn is_bipartite(&self) -> bool {
+        self.odd_cycle.is_none()
+    }
+
+    /// First edge or constraint that was rejected
+    pub fn odd_cycle(&self) -> Option<OddCycle<I>> {
+        self.odd_cycle
+    }
+
+    /// Whether `a` and `b` must be on different sides, or `None` if they are not connected
+    pub fn parity(&mut self, a: I, b: I) -> Option<bool> {
+        self.uf.diff(a, b)
+    }
+
+    /// Side of `a`, the root of every component is on side `false`
+    pub fn side(&mut self, a: I) -> bool {
+        let root = self.uf.find(a).id();
+        self.uf.diff(a, root).unwrap_or_default()
+    }
+
+    /// Gets the underlying [`UnionFind`]
+    pub fn union_find(
+        &mut self,
+    ) -> &mut UnionFind<'static, Potential, PotentialVertex<I, bool>, N> {
+        &mut self.uf
+    }
+}
+
+impl<I, const N: usize> Bipartite<I, N>
+where
+    I: Identifier + TryFrom<usize> + 'static,
+{
+    /// Writes the side of every node into `out`, which is a valid 2-coloring of every edge
+    /// that was accepted
+    ///
+    /// # Panics
+    /// Panics if `out` is shorter than `N`
+    pub fn coloring(&mut self, out: &mut [bool]) {
+        assert!(
+            out.len() >= N,
+            "Coloring slice must have at least len >= N!"
+        );
+
+        for (i, side) in out.iter_mut().take(N).enumerate() {
+            *side = self.side(identifier::<PotentialVertex<I, bool>>(i));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bipartite, OddCycle};
+
+    #[test]
+    fn test_bipartite() {
+        let mut graph = Bipartite::<u8, 6>::default();
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)];
+        for (a, b) in edges {
+            assert!(graph.add_edge(a, b).is_ok());
+        }
+        assert!(graph.is_bipartite());
+        assert_eq!(Some(false), graph.parity(0, 2));
+        assert_eq!(Some(true), graph.parity(3, 2));
+        assert_eq!(None, graph.parity(0, 4));
+
+        let mut coloring = [false; 6];
+        graph.coloring(&mut coloring);
+        for (a, b) in edges {
+            assert_ne!(coloring[a as usize], coloring[b as usize]);
+        }
+    }
+
+    #[test]
+    fn test_odd_cycle() {
+        let mut graph = Bipartite::<u32, 5>::default();
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 2).unwrap();
+        assert_eq!(Err(OddCycle { a: 2, b: 0 }), graph.add_edge(2, 0));
+        assert_eq!(Err(OddCycle { a: 1, b: 1 }), graph.add_edge(1, 1));
+        graph.add_edge(3, 4).unwrap();
+        assert!(!graph.is_bipartite());
+        assert_eq!(Some(OddCycle { a: 2, b: 0 }), graph.odd_cycle());
+
+        // rejected edges leave the coloring valid
+        let mut coloring = [false; 5];
+        graph.coloring(&mut coloring);
+        assert_eq!([false, true, false, false, true], coloring);
+    }
+
+    #[test]
+    fn test_parity_constraints() {
+        let mut constraints = Bipartite::<u16, 4>::default();
+        constraints.add_constraint(0, 1, true).unwrap();
+        constraints.add_constraint(1, 2, false).unwrap();
+        constraints.add_constraint(2, 3, true).unwrap();
+        assert!(constraints.add_constraint(3, 0, false).is_ok());
+        assert_eq!(
+            Err(OddCycle { a: 0, b: 2 }),
+            constraints.add_constraint(0, 2, false)
+        );
+        assert_eq!(Some(true), constraints.parity(2, 0));
+        assert_eq!(1, constraints.union_find().count());
+    }
+}
@@ -0,0 +1,169 @@
+//! Fixed capacity buffers that grow one node at a time, backed by `heapless::Vec`
+
+use core::marker::PhantomData;
+
+use crate::grow::{generate_forwarding_impl, Grow, Singleton};
+use crate::potential::Weighted;
+use crate::rollback::Reversible;
+use crate::{AlgorithmContainer, CapacityError, QuickUnion, UnionFind, VertexType};
+
+/// [`Bounded`] algorithm
+///
+/// Wraps algorithm `A` and keeps its buffers in `heapless::Vec`s of capacity `N`, so nodes are
+/// added at runtime with [`UnionFind::make_set`] without allocating. Every other operation is
+/// forwarded to `A`
+///
+/// # Example
+/// ```
+/// use pulau_rs::{Bounded, CapacityError, QuickUnion, UnionFind};
+///
+/// let mut uf = UnionFind::<Bounded<QuickUnion>, u8, 2>::default();
+/// let a = uf.make_set().unwrap();
+/// let b = uf.make_set().unwrap();
+/// assert_eq!(Err(CapacityError { capacity: 2 }), uf.make_set());
+/// uf.union_sets(a, b);
+/// assert!(uf.connected(a, b));
+/// ```
+#[derive(Debug, Default)]
+pub struct Bounded<A = QuickUnion> {
+    algorithm: PhantomData<A>,
+}
+
+impl<A> AlgorithmContainer for Bounded<A>
+where
+    A: Grow,
+{
+    type HeuristicContainer<'a, const N: usize> = heapless::Vec<usize, N>;
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = heapless::Vec<R, N>;
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = A::HistoryContainer<'a, R, N>;
+}
+
+impl<'a, A, T, const N: usize> Default for UnionFind<'a, Bounded<A>, T, N>
+where
+    T: VertexType + 'a,
+    A: Grow,
+    A::HistoryContainer<'a, T, N>: Default,
+{
+    fn default() -> Self {
+        Self {
+            representative: heapless::Vec::new(),
+            heuristic: heapless::Vec::new(),
+            siblings: [],
+            components: [0; 0],
+            history: Default::default(),
+            algorithm: Default::default(),
+        }
+    }
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, Bounded<A>, T, N>
+where
+    T: Singleton + 'a,
+    T::IdentifierType: TryFrom<usize>,
+    A: Grow,
+{
+    /// Adds a singleton and returns its identifier, which is the number of sets made before
+    ///
+    /// # Errors
+    /// Returns [`CapacityError`] if all `N` nodes are in use or the next identifier does not fit
+    /// into `T`, nothing is added in that case
+    pub fn make_set(&mut self) -> Result<T::IdentifierType, CapacityError> {
+        let len = self.representative.len();
+        let id = T::IdentifierType::try_from(len).map_err(|_| CapacityError { capacity: len })?;
+        self.representative
+            .push(T::singleton(id))
+            .map_err(|_| CapacityError { capacity: N })?;
+        if let Some(heuristic) = A::SINGLETON {
+            // both buffers have the same capacity and grow together
+            let _ = self.heuristic.push(heuristic);
+        }
+        Ok(id)
+    }
+}
+
+impl<A> Weighted for Bounded<A>
+where
+    A: Weighted + Grow,
+{
+    type Heuristic = A::Heuristic;
+}
+
+impl<A> Reversible for Bounded<A> where A: Reversible + Grow {}
+
+generate_forwarding_impl!(A => Bounded<A>);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Bounded, BySize, CapacityError, Potential, PotentialVertex, QuickFind, QuickUnion, Rem,
+        Rollback, UnionFind,
+    };
+
+    #[test]
+    fn test_make_set() {
+        let mut uf = UnionFind::<Bounded<QuickUnion<BySize>>, u16, 4>::default();
+        assert_eq!(0, uf.count());
+        for i in 0..4 {
+            assert_eq!(Ok(i), uf.make_set());
+        }
+        assert_eq!(Err(CapacityError { capacity: 4 }), uf.make_set());
+        assert_eq!(4, uf.representative().len());
+        assert_eq!(4, uf.heuristic().len());
+
+        uf.union_sets(0, 1);
+        uf.union_sets(3, 1);
+        assert_eq!(3, uf.set_size(0));
+        assert_eq!(2, uf.count());
+        assert!(uf.validate().is_ok());
+
+        let mut uf = UnionFind::<Bounded<QuickFind>, u32, 3>::default();
+        let [a, b] = [uf.make_set().unwrap(), uf.make_set().unwrap()];
+        assert!(uf.union_sets(a, b).is_merged());
+        assert!(uf.heuristic().is_empty());
+        assert!(uf.try_find(2).is_err());
+        assert_eq!(Ok(2), uf.make_set());
+        assert!(!uf.connected(a, 2));
+
+        let mut uf = UnionFind::<Bounded<Rem>, u8, 3>::default();
+        let [a, b, c] = [0; 3].map(|_| uf.make_set().unwrap());
+        uf.union_sets(c, a);
+        assert!(uf.connected(a, c));
+        assert!(!uf.connected(a, b));
+    }
+
+    #[test]
+    fn test_make_set_rollback() {
+        let mut uf = UnionFind::<Bounded<Rollback<BySize, 2>>, u8, 4>::default();
+        let [a, b, c] = [0; 3].map(|_| uf.make_set().unwrap());
+        uf.union_sets(a, b);
+        let checkpoint = uf.snapshot();
+        uf.union_sets(b, c);
+        assert_eq!(3, uf.set_size(a));
+        uf.rollback_to(checkpoint).unwrap();
+        assert!(!uf.connected(a, c));
+        assert_eq!(2, uf.set_size(a));
+    }
+
+    #[test]
+    fn test_make_set_potential() {
+        let mut uf = UnionFind::<Bounded<Potential>, PotentialVertex<u8, i32>, 3>::default();
+        let [a, b, c] = [0; 3].map(|_| uf.make_set().unwrap());
+        uf.union_with(a, b, 5).unwrap();
+        uf.union_with(c, b, -2).unwrap();
+        assert_eq!(Some(7), uf.diff(a, c));
+        assert!(uf.make_set().is_err());
+    }
+
+    #[test]
+    fn test_make_set_identifier_overflow() {
+        let mut uf = UnionFind::<Bounded<QuickUnion>, u8, 300>::default();
+        for i in 0..=255 {
+            assert_eq!(Ok(i), uf.make_set());
+        }
+        assert_eq!(Err(CapacityError { capacity: 256 }), uf.make_set());
+        assert_eq!(256, uf.representative().len());
+        assert_eq!(256, uf.heuristic().len());
+    }
+}
@@ -0,0 +1,284 @@
+//! Connected-component labeling of 2D images and grids
+
+use crate::partition::identifier;
+use crate::potential::Identifier;
+use crate::{Rem, UnionFind};
+
+/// Pixels that count as neighbours, see [`label`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Pixels sharing an edge
+    Four,
+    /// Pixels sharing an edge or a corner
+    Eight,
+}
+
+/// Pixel count and inclusive bounding box of a labeled component, see [`stats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Component {
+    pub pixels: usize,
+    pub left: usize,
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+}
+
+/// Labels the connected components of a row-major image of `W` by `H` pixels and returns the
+/// number of components
+///
+/// Neighbouring pixels are connected if both are `foreground` and `same` holds for them, e.g.
+/// `|_, _| true` for binary images or `PartialEq::eq` to separate regions of different values.
+/// Background pixels get label `0`, components are labeled `1..=k` in the order of their first
+/// pixel
+///
+/// `labels` doubles as the representative buffer of a [`Rem`] union find during the raster
+/// scan, whose roots are always the first pixel of their component, so the second pass resolves
+/// every label in place. Nothing else is stored, which keeps the stack usage independent of
+/// the image size
+///
+/// # Panics
+/// Panics if `image` or `labels` is shorter than `W * H`, or if `W * H` does not fit into `L`
+///
+/// # Example
+/// ```
+/// use pulau_rs::ccl::{label, Connectivity};
+///
+/// let image = [
+///     1, 1, 0, 2,
+///     0, 0, 0, 2,
+///     1, 0, 1, 0,
+/// ];
+/// let mut labels = [0u16; 12];
+/// let count = label::<_, _, 4, 3>(&image, Connectivity::Four, |&p| p != 0, |a, b| a == b, &mut labels);
+/// assert_eq!(4, count);
+/// assert_eq!([1, 1, 0, 2, 0, 0, 0, 2, 3, 0, 4, 0], labels);
+/// ```
+pub fn label<P, L, const W: usize, const H: usize>(
+    image: &[P],
+    connectivity: Connectivity,
+    mut foreground: impl FnMut(&P) -> bool,
+    mut same: impl FnMut(&P, &P) -> bool,
+    labels: &mut [L],
+) -> usize
+where
+    L: Identifier + TryFrom<usize>,
+{
+    let len = W * H;
+    assert!(
+        image.len() >= len,
+        "Image slice must have at least len >= W * H!"
+    );
+    assert!(
+        labels.len() >= len,
+        "Label slice must have at least len >= W * H!"
+    );
+
+    let labels = &mut labels[..len];
+    for (i, label) in labels.iter_mut().enumerate() {
+        *label = identifier::<L>(i);
+    }
+
+    let mut uf = UnionFind::<Rem<true>, L, 0>::new(labels);
+    for y in 0..H {
+        for x in 0..W {
+            let i = y * W + x;
+            if !foreground(&image[i]) {
+                continue;
+            }
+
+            // only the neighbours that the scan has already visited
+            let up = y > 0;
+            let neighbours = [
+                (x > 0, i.wrapping_sub(1)),
+                (up, i.wrapping_sub(W)),
+                (
+                    up && x > 0 && connectivity == Connectivity::Eight,
+                    i.wrapping_sub(W + 1),
+                ),
+                (
+                    up && x + 1 < W && connectivity == Connectivity::Eight,
+                    i.wrapping_sub(W - 1),
+                ),
+            ];
+
+            for (_, j) in neighbours.into_iter().filter(|&(exists, _)| exists) {
+                if foreground(&image[j]) && same(&image[i], &image[j]) {
+                    uf.union_sets(identifier::<L>(i), identifier::<L>(j));
+                }
+            }
+        }
+    }
+
+    // parents are never larger than their children, so every parent already holds its label
+    let mut count = 0;
+    for i in 0..len {
+        let parent = L::usize(labels[i]);
+        labels[i] = if parent != i {
+            labels[parent]
+        } else if foreground(&image[i]) {
+            count += 1;
+            identifier::<L>(count)
+        } else {
+            identifier::<L>(0)
+        };
+    }
+
+    count
+}
+
+/// Collects the pixel count and bounding box of every component labeled by [`label`],
+/// component `k` goes into `out[k - 1]`
+///
+/// # Panics
+/// Panics if `labels` is shorter than `W * H` or `out` is shorter than the number of components
+pub fn stats<L, const W: usize, const H: usize>(labels: &[L], out: &mut [Component])
+where
+    L: Identifier,
+{
+    assert!(
+        labels.len() >= W * H,
+        "Label slice must have at least len >= W * H!"
+    );
+
+    for component in out.iter_mut() {
+        *component = Component {
+            pixels: 0,
+            left: W,
+            top: H,
+            right: 0,
+            bottom: 0,
+        };
+    }
+
+    for (i, &label) in labels[..W * H].iter().enumerate() {
+        let (x, y) = (i % W, i / W);
+        if let Some(k) = L::usize(label).checked_sub(1) {
+            let component = &mut out[k];
+            component.pixels += 1;
+            component.left = component.left.min(x);
+            component.top = component.top.min(y);
+            component.right = component.right.max(x);
+            component.bottom = component.bottom.max(y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{label, stats, Component, Connectivity};
+
+    const IMAGE: [bool; 30] = {
+        let (o, x) = (false, true);
+        [
+            x, x, o, o, x, o, //
+            o, x, o, x, o, o, //
+            o, o, o, o, o, x, //
+            x, o, x, x, o, x, //
+            x, o, o, x, x, x, //
+        ]
+    };
+
+    #[test]
+    fn test_label_four() {
+        let mut labels = [0u8; 30];
+        let count =
+            label::<_, _, 6, 5>(&IMAGE, Connectivity::Four, |&p| p, |_, _| true, &mut labels);
+        assert_eq!(5, count);
+        // the last pixel joins 2 components that looked apart until then
+        assert_eq!(
+            [
+                1, 1, 0, 0, 2, 0, //
+                0, 1, 0, 3, 0, 0, //
+                0, 0, 0, 0, 0, 4, //
+                5, 0, 4, 4, 0, 4, //
+                5, 0, 0, 4, 4, 4, //
+            ],
+            labels
+        );
+    }
+
+    #[test]
+    fn test_label_eight() {
+        let mut labels = [0u32; 30];
+        let count = label::<_, _, 6, 5>(
+            &IMAGE,
+            Connectivity::Eight,
+            |&p| p,
+            |_, _| true,
+            &mut labels,
+        );
+        assert_eq!(4, count);
+        assert_eq!(
+            [
+                1, 1, 0, 0, 2, 0, //
+                0, 1, 0, 2, 0, 0, //
+                0, 0, 0, 0, 0, 3, //
+                4, 0, 3, 3, 0, 3, //
+                4, 0, 0, 3, 3, 3, //
+            ],
+            labels
+        );
+
+        let mut components = [Component {
+            pixels: 0,
+            left: 0,
+            top: 0,
+            right: 0,
+            bottom: 0,
+        }; 4];
+        stats::<_, 6, 5>(&labels, &mut components);
+        assert_eq!(
+            Component {
+                pixels: 3,
+                left: 0,
+                top: 0,
+                right: 1,
+                bottom: 1
+            },
+            components[0]
+        );
+        assert_eq!(
+            Component {
+                pixels: 7,
+                left: 2,
+                top: 2,
+                right: 5,
+                bottom: 4
+            },
+            components[2]
+        );
+        assert_eq!(2, components[3].pixels);
+    }
+
+    #[test]
+    fn test_label_regions() {
+        // 2 regions of the same value are only joined where they touch
+        let image = [
+            3, 3, 7, 7, //
+            3, 7, 7, 3, //
+            0, 0, 3, 3, //
+        ];
+        let mut labels = [0u16; 12];
+        let count = label::<_, _, 4, 3>(
+            &image,
+            Connectivity::Four,
+            |&p| p != 0,
+            |a, b| a == b,
+            &mut labels,
+        );
+        assert_eq!(3, count);
+        assert_eq!([1, 1, 2, 2, 1, 2, 2, 3, 0, 0, 3, 3], labels);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_label_short() {
+        label::<_, u8, 4, 4>(
+            &[true; 15],
+            Connectivity::Four,
+            |&p| p,
+            |_, _| true,
+            &mut [0; 16],
+        );
+    }
+}
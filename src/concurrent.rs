@@ -0,0 +1,323 @@
+//! Lock-free concurrent union find implementation
+
+#[cfg(loom)]
+use loom::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+#[cfg(not(loom))]
+use core::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+use crate::UnionResult;
+
+/// Atomic integer that stores the parent of a node in [`AtomicUnionFind`]
+pub trait AtomicParent {
+    /// Creates a parent pointing at `id`
+    ///
+    /// # Panics
+    /// Panics if `id` does not fit into the atomic
+    fn new(id: usize) -> Self;
+
+    /// Loads the current parent
+    fn load(&self, order: Ordering) -> usize;
+
+    /// Replaces the parent with `new` if it is still `current`
+    ///
+    /// # Errors
+    /// Returns the current parent if it is not `current`
+    fn compare_exchange(
+        &self,
+        current: usize,
+        new: usize,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<usize, usize>;
+}
+
+impl AtomicParent for AtomicUsize {
+    #[inline(always)]
+    fn new(id: usize) -> Self {
+        AtomicUsize::new(id)
+    }
+
+    #[inline(always)]
+    fn load(&self, order: Ordering) -> usize {
+        AtomicUsize::load(self, order)
+    }
+
+    #[inline(always)]
+    fn compare_exchange(
+        &self,
+        current: usize,
+        new: usize,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<usize, usize> {
+        AtomicUsize::compare_exchange(self, current, new, success, failure)
+    }
+}
+
+impl AtomicParent for AtomicU32 {
+    #[inline(always)]
+    fn new(id: usize) -> Self {
+        match u32::try_from(id) {
+            Ok(id) => AtomicU32::new(id),
+            Err(_) => panic!("Index {} does not fit into the identifier type!", id),
+        }
+    }
+
+    #[inline(always)]
+    fn load(&self, order: Ordering) -> usize {
+        AtomicU32::load(self, order) as usize
+    }
+
+    #[inline(always)]
+    fn compare_exchange(
+        &self,
+        current: usize,
+        new: usize,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<usize, usize> {
+        // both ids are below `N`, which `new` has already checked to fit
+        AtomicU32::compare_exchange(self, current as u32, new as u32, success, failure)
+            .map(|parent| parent as usize)
+            .map_err(|parent| parent as usize)
+    }
+}
+
+/// Seed of [`AtomicUnionFind::default`]
+const DEFAULT_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Lock-free union find over `N` nodes that is shared between threads by reference
+///
+/// Roots are linked with a single CAS following a random total order of the nodes, which is a
+/// bijective hash of every index and a seed. The lower root always goes under the higher one, so
+/// priorities only ever grow along a path and concurrent links can never form a cycle. Finds
+/// halve the path with CAS as well and simply move on if another thread got there first, every
+/// operation is lock-free
+///
+/// This is the randomized linking of Jayanti and Tarjan, which keeps the expected work per
+/// operation within their `α`-style bound as long as the order of the unions does not depend
+/// on the seed. [`AtomicUnionFind::default`] uses a fixed seed, pick a secret one with
+/// [`AtomicUnionFind::with_seed`] when the unions may come from an adversary
+///
+/// This algorithm is parameterized by the following
+/// - `N` - number of nodes
+/// - `P` - Parent type. Available types: `AtomicUsize`, `AtomicU32`
+pub struct AtomicUnionFind<const N: usize, P = AtomicUsize> {
+    parents: [P; N],
+    seed: u64,
+}
+
+impl<const N: usize, P> Default for AtomicUnionFind<N, P>
+where
+    P: AtomicParent,
+{
+    fn default() -> Self {
+        Self::with_seed(DEFAULT_SEED)
+    }
+}
+
+impl<const N: usize, P> AtomicUnionFind<N, P>
+where
+    P: AtomicParent,
+{
+    /// Creates `N` singletons whose link order is derived from `seed`
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            parents: core::array::from_fn(P::new),
+            seed,
+        }
+    }
+
+    /// Position of `a` in the link order, the splitmix64 finalizer keeps it unique per node
+    fn priority(&self, a: usize) -> u64 {
+        let mut x = a as u64 ^ self.seed;
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        x ^ (x >> 31)
+    }
+
+    /// Finds the root of `a`. The root may already be stale by the time this returns
+    /// if another thread links it concurrently
+    pub fn find(&self, mut a: usize) -> usize {
+        assert!(a < N);
+
+        loop {
+            let parent = self.parents[a].load(Ordering::Acquire);
+            if parent == a {
+                return a;
+            }
+
+            let grandparent = self.parents[parent].load(Ordering::Acquire);
+            if grandparent != parent {
+                // losing the race only means somebody else shortened the path already
+                let _ = self.parents[a].compare_exchange(
+                    parent,
+                    grandparent,
+                    Ordering::Release,
+                    Ordering::Relaxed,
+                );
+            }
+            a = grandparent;
+        }
+    }
+
+    /// Checks whether 2 nodes are connected to each other
+    pub fn connected(&self, a: usize, b: usize) -> bool {
+        loop {
+            let (root_a, root_b) = (self.find(a), self.find(b));
+            if root_a == root_b {
+                return true;
+            }
+
+            // `root_a` still being a root means the 2 were apart when `root_b` was found
+            if self.parents[root_a].load(Ordering::Acquire) == root_a {
+                return false;
+            }
+        }
+    }
+
+    /// Unions the components of `a` and `b`, returning the roots as they were at the time
+    /// of linking
+    pub fn union_sets(&self, a: usize, b: usize) -> UnionResult<usize> {
+        loop {
+            let (root_a, root_b) = (self.find(a), self.find(b));
+            if root_a == root_b {
+                return UnionResult::AlreadyConnected { root: root_a };
+            }
+
+            let (absorbed, root) = if self.priority(root_a) < self.priority(root_b) {
+                (root_a, root_b)
+            } else {
+                (root_b, root_a)
+            };
+
+            // fails if `absorbed` stopped being a root in the meantime, then look again
+            if self.parents[absorbed]
+                .compare_exchange(absorbed, root, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return UnionResult::Merged { root, absorbed };
+            }
+        }
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    extern crate std;
+
+    use super::AtomicUnionFind;
+    use crate::UnionResult;
+    use core::sync::atomic::AtomicU32;
+
+    #[test]
+    fn test_atomic() {
+        let uf = AtomicUnionFind::<10>::default();
+        let UnionResult::Merged { root, absorbed } = uf.union_sets(4, 3) else {
+            panic!("4 and 3 start out apart");
+        };
+        assert_eq!(7, root + absorbed);
+        uf.union_sets(3, 8);
+        uf.union_sets(6, 5);
+        uf.union_sets(9, 4);
+        assert!(uf.connected(3, 9));
+        assert!(!uf.connected(5, 9));
+        let root = uf.find(3);
+        assert_eq!(UnionResult::AlreadyConnected { root }, uf.union_sets(8, 4));
+    }
+
+    #[test]
+    fn test_atomic_seed() {
+        // a path added in index order ends up as one component under every seed
+        const N: usize = 1024;
+        for seed in [0, 1, u64::MAX] {
+            let uf = AtomicUnionFind::<N>::with_seed(seed);
+            for i in 1..N {
+                uf.union_sets(i - 1, i);
+            }
+            let root = uf.find(0);
+            assert!((0..N).all(|i| uf.find(i) == root));
+        }
+
+        // priorities are unique, so 2 roots are always ordered
+        let uf = AtomicUnionFind::<N>::default();
+        let mut priorities = (0..N).map(|i| uf.priority(i)).collect::<std::vec::Vec<_>>();
+        priorities.sort_unstable();
+        priorities.dedup();
+        assert_eq!(N, priorities.len());
+    }
+
+    #[test]
+    fn test_atomic_threads() {
+        const N: usize = 256;
+        let uf = AtomicUnionFind::<N, AtomicU32>::default();
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                let uf = &uf;
+                s.spawn(move || {
+                    for i in (t..N - 2).step_by(4) {
+                        uf.union_sets(i, i + 2);
+                    }
+                });
+            }
+        });
+
+        // evens and odds form 2 chains
+        for i in 0..N - 2 {
+            assert!(uf.connected(i, i + 2));
+            assert!(!uf.connected(i, i + 1));
+        }
+    }
+}
+
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::AtomicUnionFind;
+    use loom::sync::Arc;
+    use loom::thread;
+
+    #[test]
+    fn test_concurrent_unions() {
+        loom::model(|| {
+            let uf = Arc::new(AtomicUnionFind::<3>::default());
+            let other = Arc::clone(&uf);
+
+            let handle = thread::spawn(move || other.union_sets(0, 1).is_merged());
+            let merged = uf.union_sets(1, 2).is_merged();
+            assert!(handle.join().unwrap() && merged);
+
+            assert!(uf.connected(0, 2));
+            assert_eq!(uf.find(2), uf.find(0));
+        });
+    }
+
+    #[test]
+    fn test_concurrent_same_union() {
+        loom::model(|| {
+            let uf = Arc::new(AtomicUnionFind::<2>::default());
+            let other = Arc::clone(&uf);
+
+            let handle = thread::spawn(move || other.union_sets(0, 1).is_merged());
+            let merged = uf.union_sets(1, 0).is_merged();
+            // exactly one of the 2 links wins
+            assert!(handle.join().unwrap() ^ merged);
+        });
+    }
+
+    #[test]
+    fn test_concurrent_find() {
+        loom::model(|| {
+            let uf = Arc::new(AtomicUnionFind::<4>::default());
+            uf.union_sets(0, 1);
+            let other = Arc::clone(&uf);
+
+            let handle = thread::spawn(move || other.union_sets(1, 3));
+            assert!(uf.connected(0, 1));
+            uf.union_sets(2, 3);
+            handle.join().unwrap();
+            assert!(uf.connected(0, 2));
+        });
+    }
+}
@@ -0,0 +1,201 @@
+//! Component counting wrapper
+
+use core::marker::PhantomData;
+
+use crate::{
+    count_roots, AlgorithmContainer, Connected, Find, HeuristicType, QuickUnion, SetSize, Union,
+    UnionFind, UnionFindError, UnionResult, Validate, VertexType,
+};
+
+/// [`Counted`] algorithm
+///
+/// Wraps algorithm `A` and keeps a live count of the components, which makes
+/// [`UnionFind::count`] `O(1)`. Every other operation is forwarded to `A`
+///
+/// Owned buffers are constructed with [`Default`], borrowed buffers by converting
+/// an existing [`UnionFind`] with [`From`]
+#[derive(Debug, Default)]
+pub struct Counted<A = QuickUnion> {
+    algorithm: PhantomData<A>,
+}
+
+impl<A> AlgorithmContainer for Counted<A>
+where
+    A: AlgorithmContainer,
+{
+    type HeuristicContainer<'a, const N: usize> = A::HeuristicContainer<'a, N>;
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> =
+        A::RepresentativeContainer<'a, R, N>;
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = A::SiblingContainer<'a, R, N>;
+    type ComponentContainer = [usize; 1];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = A::HistoryContainer<'a, R, N>;
+}
+
+impl<'a, A, T, const N: usize> From<UnionFind<'a, A, T, N>> for UnionFind<'a, Counted<A>, T, N>
+where
+    T: VertexType,
+    A: AlgorithmContainer,
+{
+    fn from(uf: UnionFind<'a, A, T, N>) -> Self {
+        let components = [count_roots(uf.representative.as_ref())];
+
+        Self {
+            representative: uf.representative,
+            heuristic: uf.heuristic,
+            siblings: uf.siblings,
+            components,
+            history: uf.history,
+            algorithm: Default::default(),
+        }
+    }
+}
+
+impl<'a, A, T, const N: usize> Default for UnionFind<'a, Counted<A>, T, N>
+where
+    T: VertexType,
+    A: AlgorithmContainer,
+    UnionFind<'a, A, T, N>: Default,
+{
+    fn default() -> Self {
+        UnionFind::<'a, A, T, N>::default().into()
+    }
+}
+
+impl<A, T> Connected<T> for Counted<A>
+where
+    T: VertexType,
+    A: Connected<T>,
+{
+    fn connected(representative: &mut [T], a: T::IdentifierType, b: T::IdentifierType) -> bool {
+        A::connected(representative, a, b)
+    }
+
+    fn try_connected(
+        representative: &mut [T],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<bool, UnionFindError> {
+        A::try_connected(representative, a, b)
+    }
+}
+
+impl<A, T> Union<T> for Counted<A>
+where
+    T: VertexType,
+    A: Union<T>,
+{
+    fn union_sets<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> UnionResult<T::IdentifierType> {
+        A::union_sets(representative, heuristic, a, b)
+    }
+
+    fn try_union_sets<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<UnionResult<T::IdentifierType>, UnionFindError> {
+        A::try_union_sets(representative, heuristic, a, b)
+    }
+}
+
+impl<A, T> Find<T> for Counted<A>
+where
+    T: VertexType,
+    A: Find<T>,
+{
+    fn find(representative: &mut [T], a: T::IdentifierType) -> T {
+        A::find(representative, a)
+    }
+
+    fn try_find(representative: &mut [T], a: T::IdentifierType) -> Result<T, UnionFindError> {
+        A::try_find(representative, a)
+    }
+}
+
+impl<A, T> SetSize<T> for Counted<A>
+where
+    T: VertexType,
+    A: SetSize<T>,
+{
+    fn set_size<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &[S],
+        a: T::IdentifierType,
+    ) -> usize {
+        A::set_size(representative, heuristic, a)
+    }
+}
+
+impl<A, T> Validate<T> for Counted<A>
+where
+    T: VertexType,
+    A: Validate<T>,
+{
+    fn validate<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+    ) -> Result<(), UnionFindError> {
+        A::validate(representative, heuristic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ByRank, BySize, Counted, QuickFind, QuickUnion, UnionFind, Unweighted};
+    use core::mem;
+
+    #[test]
+    fn test_count() {
+        let mut uf = UnionFind::<Counted<QuickFind>, u32, 10>::default();
+        assert_eq!(10, uf.count());
+        uf.union_sets(4, 3);
+        uf.union_sets(3, 8);
+        uf.union_sets(8, 4);
+        assert_eq!(8, uf.count());
+        assert!(uf.try_union_sets(6, 10).is_err());
+        assert_eq!(8, uf.count());
+
+        let mut uf = UnionFind::<Counted<QuickUnion<BySize>>, u8, 10>::default();
+        uf.union_sets(1, 2);
+        uf.union_sets(5, 6);
+        uf.union_sets(2, 6);
+        uf.union_sets(1, 5);
+        assert_eq!(7, uf.count());
+
+        let mut uf = UnionFind::<Counted<QuickUnion<Unweighted, false>>, u8, 10>::default();
+        uf.union_sets(1, 2);
+        assert_eq!(9, uf.count());
+    }
+
+    #[test]
+    fn test_count_slice() {
+        let mut representative = [0u8, 0, 2, 3, 4];
+        let mut heuristic = [1, 0, 0, 0, 0];
+        let uf =
+            UnionFind::<QuickUnion<ByRank<true>>, u8, 5>::new(&mut representative, &mut heuristic);
+        assert_eq!(4, uf.count());
+
+        let mut uf = UnionFind::<Counted<QuickUnion<ByRank<true>>>, u8, 5>::from(uf);
+        assert_eq!(4, uf.count());
+        uf.union_sets(2, 3);
+        uf.union_sets(1, 3);
+        assert_eq!(2, uf.count());
+    }
+
+    #[test]
+    fn test_count_sz() {
+        assert_eq!(
+            mem::size_of::<UnionFind::<'_, QuickUnion, u32, 10>>() + mem::size_of::<usize>(),
+            mem::size_of::<UnionFind::<'_, Counted<QuickUnion>, u32, 10>>()
+        );
+        assert_eq!(
+            mem::size_of::<&'_ [u32]>() + mem::size_of::<usize>(),
+            mem::size_of::<UnionFind::<'_, Counted<QuickFind<true>>, u32, 10>>()
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Incremental cycle detection for undirected edge streams
+
+use crate::partition::identifier;
+use crate::potential::Identifier;
+use crate::{BySize, QuickUnion, UnionFind};
+
+/// Edge that closed a cycle, see [`CycleDetector`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle<I> {
+    /// First endpoint of the edge as it was added
+    pub a: I,
+    /// Second endpoint of the edge as it was added
+    pub b: I,
+}
+
+/// Storage for the spanning forest of a [`CycleDetector`], `()` stores nothing
+pub trait Forest<I> {
+    /// Adds the tree edge between `small` and `large`, which are in different trees and `small`
+    /// is in the smaller one
+    fn link(&mut self, small: I, large: I);
+}
+
+impl<I> Forest<I> for () {
+    fn link(&mut self, _: I, _: I) {}
+}
+
+/// Spanning forest of `N` nodes stored as parent pointers, every root is its own parent
+///
+/// Linking re-roots the smaller tree at its endpoint, which takes `O(depth)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParentForest<I, const N: usize> {
+    parent: [I; N],
+}
+
+impl<I, const N: usize> Default for ParentForest<I, N>
+where
+    I: Identifier + TryFrom<usize>,
+{
+    fn default() -> Self {
+        Self {
+            parent: core::array::from_fn(identifier::<I>),
+        }
+    }
+}
+
+impl<I, const N: usize> ParentForest<I, N>
+where
+    I: Identifier,
+{
+    /// Gets the parent buffer
+    pub fn parents(&self) -> &[I; N] {
+        &self.parent
+    }
+
+    fn parent(&self, a: usize) -> usize {
+        I::usize(self.parent[a])
+    }
+
+    /// Reverses the path from `a` to its root so that `a` becomes the root
+    fn evert(&mut self, a: I) {
+        let (mut previous, mut at) = (a, self.parent[I::usize(a)]);
+        self.parent[I::usize(a)] = a;
+        while I::usize(at) != I::usize(previous) {
+            let next = self.parent[I::usize(at)];
+            self.parent[I::usize(at)] = previous;
+            if I::usize(next) == I::usize(at) {
+                break;
+            }
+            (previous, at) = (at, next);
+        }
+    }
+
+    fn depth(&self, mut a: usize) -> usize {
+        let mut depth = 0;
+        while self.parent(a) != a {
+            a = self.parent(a);
+            depth += 1;
+        }
+        depth
+    }
+}
+
+impl<I, const N: usize> Forest<I> for ParentForest<I, N>
+where
+    I: Identifier,
+{
+    fn link(&mut self, small: I, large: I) {
+        self.evert(small);
+        self.parent[I::usize(small)] = large;
+    }
+}
+
+/// Ingests the edges of an undirected graph on `N` nodes one by one and reports the edges that
+/// close a cycle, which are not added
+///
+/// With a [`ParentForest`] as `F` every accepted edge is also kept in a spanning forest, so
+/// [`CycleDetector::cycle_path`] can reconstruct the cycle itself
+///
+/// # Example
+/// ```
+/// use pulau_rs::cycle::{Cycle, CycleDetector, ParentForest};
+///
+/// let mut graph = CycleDetector::<u8, 5, ParentForest<u8, 5>>::default();
+/// let edges = [(0, 1), (1, 2), (3, 4), (2, 3), (4, 1)];
+/// assert_eq!(Some(Cycle { a: 4, b: 1 }), graph.find_cycle(edges));
+///
+/// let mut path = [0; 5];
+/// let len = graph.cycle_path(&mut path).unwrap();
+/// assert_eq!([4, 3, 2, 1], path[..len]);
+/// ```
+pub struct CycleDetector<I, const N: usize, F = ()>
+where
+    I: Identifier + 'static,
+{
+    uf: UnionFind<'static, QuickUnion<BySize>, I, N>,
+    forest: F,
+    cycle: Option<Cycle<I>>,
+}
+
+impl<I, const N: usize, F> Default for CycleDetector<I, N, F>
+where
+    I: Identifier + 'static,
+    F: Default,
+    UnionFind<'static, QuickUnion<BySize>, I, N>: Default,
+{
+    fn default() -> Self {
+        Self {
+            uf: UnionFind::<'static, QuickUnion<BySize>, I, N>::default(),
+            forest: F::default(),
+            cycle: None,
+        }
+    }
+}
+
+impl<I, const N: usize, F> CycleDetector<I, N, F>
+where
+    I: Identifier + 'static,
+    F: Forest<I>,
+{
+    /// Adds an edge unless `a` and `b` are already connected
+    ///
+    /// # Errors
+    /// Returns [`Cycle`] if the edge closes a cycle, the edge is not added
+    pub fn add_edge(&mut self, a: I, b: I) -> Result<(), Cycle<I>> {
+        if self.uf.connected(a, b) {
+            let cycle = Cycle { a, b };
+            self.cycle.get_or_insert(cycle);
+            return Err(cycle);
+        }
+
+        let (small, large) = if self.uf.set_size(a) < self.uf.set_size(b) {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.forest.link(small, large);
+        self.uf.union_sets(a, b);
+        Ok(())
+    }
+
+    /// Adds edges until one of them closes a cycle and returns that edge, the remaining edges
+    /// are not consumed
+    pub fn find_cycle(&mut self, edges: impl IntoIterator<Item = (I, I)>) -> Option<Cycle<I>> {
+        edges
+            .into_iter()
+            .find_map(|(a, b)| self.add_edge(a, b).err())
+    }
+
+    /// Whether any edge added so far closed a cycle
+    pub fn has_cycle(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    /// First edge that closed a cycle
+    pub fn cycle(&self) -> Option<Cycle<I>> {
+        self.cycle
+    }
+
+    /// Checks whether 2 nodes are connected by the edges added so far
+    pub fn connected(&mut self, a: I, b: I) -> bool {
+        self.uf.connected(a, b)
+    }
+
+    /// Gets the spanning forest
+    pub fn forest(&self) -> &F {
+        &self.forest
+    }
+
+    /// Gets the underlying [`UnionFind`]
+    pub fn union_find(&self) -> &UnionFind<'static, QuickUnion<BySize>, I, N> {
+        &self.uf
+    }
+}
+
+impl<I, const N: usize> CycleDetector<I, N, ParentForest<I, N>>
+where
+    I: Identifier + TryFrom<usize> + 'static,
+{
+    /// Writes the nodes on the forest path from `a` to `b` into the front of `out` and returns
+    /// their number, or `None` if `a` and `b` are not connected. Takes `O(N)`
+    ///
+    /// # Panics
+    /// Panics if `out` is shorter than the path
+    pub fn path(&mut self, a: I, b: I, out: &mut [I]) -> Option<usize> {
+        if !self.uf.connected(a, b) {
+            return None;
+        }
+
+        let forest = &self.forest;
+        let (mut x, mut y) = (I::usize(a), I::usize(b));
+        let (mut dx, mut dy) = (forest.depth(x), forest.depth(y));
+        let (depth_a, depth_b) = (dx, dy);
+        while dx > dy {
+            (x, dx) = (forest.parent(x), dx - 1);
+        }
+        while dy > dx {
+            (y, dy) = (forest.parent(y), dy - 1);
+        }
+        while x != y {
+            (x, y) = (forest.parent(x), forest.parent(y));
+            dx -= 1;
+        }
+
+        // up from `a` to the common ancestor, then down to `b`
+        let (up, down) = (depth_a - dx, depth_b - dx);
+        let len = up + down + 1;
+        assert!(
+            out.len() >= len,
+            "Path slice must have at least len >= number of nodes on the path!"
+        );
+
+        let mut at = I::usize(a);
+        for node in out[..=up].iter_mut() {
+            *node = identifier::<I>(at);
+            at = forest.parent(at);
+        }
+        let mut at = I::usize(b);
+        for node in out[up + 1..len].iter_mut().rev() {
+            *node = identifier::<I>(at);
+            at = forest.parent(at);
+        }
+
+        Some(len)
+    }
+
+    /// Writes the cycle closed by the first rejected edge `(a, b)` into the front of `out` as
+    /// the path from `a` to `b`, the closing edge leads back to `a`. Returns the number of
+    /// nodes or `None` if there is no cycle
+    ///
+    /// # Panics
+    /// Panics if `out` is shorter than the cycle
+    pub fn cycle_path(&mut self, out: &mut [I]) -> Option<usize> {
+        let Cycle { a, b } = self.cycle?;
+        self.path(a, b, out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cycle, CycleDetector, ParentForest};
+
+    #[test]
+    fn test_cycle_detector() {
+        let mut graph = CycleDetector::<u16, 6>::default();
+        assert_eq!(None, graph.find_cycle([(0, 1), (2, 3), (1, 2)]));
+        assert!(!graph.has_cycle());
+        assert_eq!(Err(Cycle { a: 3, b: 0 }), graph.add_edge(3, 0));
+        assert_eq!(Err(Cycle { a: 5, b: 5 }), graph.add_edge(5, 5));
+        assert!(graph.add_edge(4, 5).is_ok());
+        assert_eq!(Some(Cycle { a: 3, b: 0 }), graph.cycle());
+        assert!(!graph.connected(0, 4));
+        assert_eq!(2, graph.union_find().count());
+    }
+
+    #[test]
+    fn test_find_cycle_stops() {
+        let mut graph = CycleDetector::<u8, 4>::default();
+        let mut edges = [(0, 1), (1, 0), (2, 3)].into_iter();
+        assert_eq!(Some(Cycle { a: 1, b: 0 }), graph.find_cycle(&mut edges));
+        assert_eq!(Some((2, 3)), edges.next());
+    }
+
+    #[test]
+    fn test_cycle_path() {
+        // 2 paths that get joined at both ends, which re-roots one of them
+        let mut graph = CycleDetector::<u32, 8, ParentForest<u32, 8>>::default();
+        let edges = [
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (4, 5),
+            (5, 6),
+            (7, 6),
+            (3, 7),
+            (0, 4),
+        ];
+        assert_eq!(Some(Cycle { a: 0, b: 4 }), graph.find_cycle(edges));
+
+        let mut path = [0; 8];
+        assert_eq!(Some(8), graph.cycle_path(&mut path));
+        assert_eq!([0, 1, 2, 3, 7, 6, 5, 4], path);
+
+        assert_eq!(Some(1), graph.path(5, 5, &mut path));
+        assert_eq!(5, path[0]);
+        assert_eq!(Some(3), graph.path(6, 3, &mut path));
+        assert_eq!([6, 7, 3], path[..3]);
+
+        let mut graph = CycleDetector::<u8, 3, ParentForest<u8, 3>>::default();
+        graph.add_edge(0, 1).unwrap();
+        assert_eq!(None, graph.path(0, 2, &mut [0; 3]));
+        assert_eq!(None, graph.cycle_path(&mut [0; 3]));
+        assert_eq!(&[0, 0, 2], graph.forest().parents());
+    }
+}
@@ -0,0 +1,277 @@
+//! Forest export for debugging
+//!
+//! Both formats are written to any [`fmt::Write`] sink without allocating, e.g. a
+//! `heapless::String` or a serial port
+
+use core::fmt;
+
+use crate::{
+    AlgorithmContainer, ByRank, BySize, Counted, HeuristicType, Linked, Potential, QuickFind,
+    QuickUnionWith, Rem, Rollback, UnionFind, Unweighted, VertexType,
+};
+
+/// Names the values in the heuristic buffer of an algorithm
+pub trait HeuristicLabel {
+    /// Empty for algorithms without a heuristic buffer
+    const HEURISTIC: &'static str;
+}
+
+impl<const IS_SLICE: bool> HeuristicLabel for QuickFind<IS_SLICE> {
+    const HEURISTIC: &'static str = "";
+}
+
+impl<const IS_SLICE: bool> HeuristicLabel for Rem<IS_SLICE> {
+    const HEURISTIC: &'static str = "";
+}
+
+impl<const IS_SLICE: bool, C> HeuristicLabel for QuickUnionWith<Unweighted<IS_SLICE>, C> {
+    const HEURISTIC: &'static str = "";
+}
+
+impl<const IS_SLICE: bool, C> HeuristicLabel for QuickUnionWith<ByRank<IS_SLICE>, C> {
+    const HEURISTIC: &'static str = "rank";
+}
+
+impl<const IS_SLICE: bool, C> HeuristicLabel for QuickUnionWith<BySize<IS_SLICE>, C> {
+    const HEURISTIC: &'static str = "size";
+}
+
+impl<const IS_SLICE: bool, const CAPACITY: usize> HeuristicLabel
+    for Rollback<ByRank<IS_SLICE>, CAPACITY>
+{
+    const HEURISTIC: &'static str = "rank";
+}
+
+impl<const IS_SLICE: bool, const CAPACITY: usize> HeuristicLabel
+    for Rollback<BySize<IS_SLICE>, CAPACITY>
+{
+    const HEURISTIC: &'static str = "size";
+}
+
+impl<const IS_SLICE: bool> HeuristicLabel for Potential<ByRank<IS_SLICE>> {
+    const HEURISTIC: &'static str = "rank";
+}
+
+impl<const IS_SLICE: bool> HeuristicLabel for Potential<BySize<IS_SLICE>> {
+    const HEURISTIC: &'static str = "size";
+}
+
+impl<A> HeuristicLabel for Counted<A>
+where
+    A: HeuristicLabel,
+{
+    const HEURISTIC: &'static str = A::HEURISTIC;
+}
+
+#[cfg(feature = "heapless")]
+impl<A> HeuristicLabel for crate::Bounded<A>
+where
+    A: HeuristicLabel,
+{
+    const HEURISTIC: &'static str = A::HEURISTIC;
+}
+
+#[cfg(feature = "alloc")]
+impl<A> HeuristicLabel for crate::Growable<A>
+where
+    A: HeuristicLabel,
+{
+    const HEURISTIC: &'static str = A::HEURISTIC;
+}
+
+impl<W, A> HeuristicLabel for crate::Mapped<W, A>
+where
+    A: HeuristicLabel,
+{
+    const HEURISTIC: &'static str = A::HEURISTIC;
+}
+
+impl<A, const IS_SLICE: bool> HeuristicLabel for Linked<A, IS_SLICE>
+where
+    A: HeuristicLabel,
+{
+    const HEURISTIC: &'static str = A::HEURISTIC;
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, A, T, N>
+where
+    T: VertexType,
+    A: AlgorithmContainer + HeuristicLabel,
+{
+    /// Writes the parent forest as a Graphviz DOT digraph
+    ///
+    /// Every node is labeled with its index and heuristic, edges point from a node to its
+    /// parent and roots are drawn as double circles
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if `out` fails
+    pub fn write_dot<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        let representative = self.representative.as_ref();
+
+        out.write_str("digraph UnionFind {\n")?;
+        for (i, node) in representative.iter().enumerate() {
+            write!(out, "    {} [label=\"{}", i, i)?;
+            self.write_heuristic(out, i, "\\n")?;
+            out.write_char('"')?;
+            if T::usize(node.id()) == i {
+                out.write_str(", shape=doublecircle")?;
+            }
+            out.write_str("];\n")?;
+        }
+        for (i, node) in representative.iter().enumerate() {
+            let parent = T::usize(node.id());
+            if parent != i {
+                writeln!(out, "    {} -> {};", i, parent)?;
+            }
+        }
+        out.write_str("}\n")
+    }
+
+    /// Writes the parent forest as an indented tree per component, roots in ascending order
+    ///
+    /// ```text
+    /// 0 (rank 2)
+    /// ├── 1 (rank 1)
+    /// │   └── 2 (rank 0)
+    /// └── 4 (rank 0)
+    /// 3 (rank 0)
+    /// ```
+    ///
+    /// Children are found by scanning every node, so this takes `O(N² · depth)`.
+    /// Nodes whose parent chain never reaches a root are left out
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if `out` fails
+    pub fn write_tree<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        let representative = self.representative.as_ref();
+        let len = representative.len();
+        let parent = |i: usize| T::usize(representative[i].id());
+        // first child of `p` that is not smaller than `from`
+        let child = |p: usize, from: usize| (from..len).find(|&i| i != p && parent(i) == p);
+        let has_next_sibling = |i: usize| child(parent(i), i + 1).is_some();
+
+        for root in (0..len).filter(|&i| parent(i) == i) {
+            self.write_tree_node(out, root)?;
+
+            let mut next = child(root, 0);
+            while let Some(node) = next {
+                let mut depth = 0;
+                let mut at = node;
+                while at != root {
+                    at = parent(at);
+                    depth += 1;
+                }
+
+                // an ancestor with siblings after it still has a branch running past this line
+                for level in 1..depth {
+                    let ancestor = (level..depth).fold(node, |at, _| parent(at));
+                    out.write_str(if has_next_sibling(ancestor) {
+                        "│   "
+                    } else {
+                        "    "
+                    })?;
+                }
+                out.write_str(if has_next_sibling(node) {
+                    "├── "
+                } else {
+                    "└── "
+                })?;
+                self.write_tree_node(out, node)?;
+
+                // pre-order: first child, else the next sibling of the closest ancestor
+                next = child(node, 0);
+                let mut at = node;
+                while next.is_none() && at != root {
+                    next = child(parent(at), at + 1);
+                    at = parent(at);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_tree_node<W: fmt::Write>(&self, out: &mut W, i: usize) -> fmt::Result {
+        write!(out, "{}", i)?;
+        if self.heuristic.as_ref().get(i).is_some() {
+            out.write_str(" (")?;
+            self.write_heuristic(out, i, "")?;
+            out.write_char(')')?;
+        }
+        out.write_char('\n')
+    }
+
+    /// Writes `separator` and the heuristic of `i` if there is one
+    fn write_heuristic<W: fmt::Write>(
+        &self,
+        out: &mut W,
+        i: usize,
+        separator: &str,
+    ) -> fmt::Result {
+        match self.heuristic.as_ref().get(i) {
+            Some(heuristic) => write!(out, "{}{} {}", separator, A::HEURISTIC, heuristic.get()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::String;
+
+    use crate::{ByRank, Counted, QuickFind, QuickUnion, UnionFind, Unweighted};
+
+    #[test]
+    fn test_write_tree() {
+        let mut representative = [0u8, 0, 1, 3, 0, 5];
+        let mut heuristic = [2, 1, 0, 0, 0, 0];
+        let uf =
+            UnionFind::<QuickUnion<ByRank<true>>, u8, 6>::new(&mut representative, &mut heuristic);
+        let mut out = String::<256>::new();
+        uf.write_tree(&mut out).unwrap();
+        assert_eq!(
+            "0 (rank 2)\n\
+             ├── 1 (rank 1)\n\
+             │   └── 2 (rank 0)\n\
+             └── 4 (rank 0)\n\
+             3 (rank 0)\n\
+             5 (rank 0)\n",
+            out.as_str()
+        );
+
+        let mut uf = UnionFind::<QuickUnion<Unweighted>, u16, 5>::default();
+        uf.union_sets(0, 1);
+        uf.union_sets(2, 1);
+        uf.union_sets(1, 3);
+        uf.union_sets(4, 0);
+        let mut out = String::<128>::new();
+        uf.write_tree(&mut out).unwrap();
+        assert_eq!("3\n├── 0\n├── 1\n│   └── 2\n└── 4\n", out.as_str());
+    }
+
+    #[test]
+    fn test_write_dot() {
+        let mut uf = UnionFind::<Counted<QuickUnion>, u32, 3>::default();
+        uf.union_sets(2, 1);
+        let mut out = String::<256>::new();
+        uf.write_dot(&mut out).unwrap();
+        assert_eq!(
+            "digraph UnionFind {\n    \
+             0 [label=\"0\\nrank 0\", shape=doublecircle];\n    \
+             1 [label=\"1\\nrank 0\"];\n    \
+             2 [label=\"2\\nrank 1\", shape=doublecircle];\n    \
+             1 -> 2;\n\
+             }\n",
+            out.as_str()
+        );
+
+        let mut uf = UnionFind::<QuickFind, u8, 2>::default();
+        uf.union_sets(0, 1);
+        let mut out = String::<128>::new();
+        uf.write_dot(&mut out).unwrap();
+        assert_eq!(
+            "digraph UnionFind {\n    0 [label=\"0\"];\n    1 [label=\"1\", shape=doublecircle];\n    0 -> 1;\n}\n",
+            out.as_str()
+        );
+    }
+}
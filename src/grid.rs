@@ -0,0 +1,313 @@
+//! Connectivity of N-dimensional grids, e.g. voxel volumes
+
+use crate::partition::identifier;
+use crate::{BySize, QuickUnion, UnionFind, UnionResult};
+
+/// Cells that count as neighbours, see [`Grid::connect`]
+///
+/// | Neighbourhood | 2D  | 3D   |
+/// | :------------ | --: | ---: |
+/// | `Faces`       | 4   | 6    |
+/// | `Edges`       | 8   | 18   |
+/// | `Corners`     | 8   | 26   |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Cells that differ by one along a single axis
+    Faces,
+    /// Cells that differ by one along at most 2 axes
+    Edges,
+    /// Cells that differ by at most one along every axis
+    Corners,
+}
+
+impl Neighbourhood {
+    /// Largest number of axes along which neighbours may differ
+    fn reach(self, dimensions: usize) -> usize {
+        match self {
+            Self::Faces => 1,
+            Self::Edges => 2,
+            Self::Corners => dimensions,
+        }
+    }
+}
+
+/// Number of cells and inclusive bounding box of a component, see [`Grid::extents`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extent<const D: usize> {
+    pub cells: usize,
+    pub min: [usize; D],
+    pub max: [usize; D],
+}
+
+/// [`UnionFind`] over the `N` cells of a `D`-dimensional grid, addressed by coordinates
+///
+/// Cell `[x, y, z, ..]` is stored at index `x + shape[0] * (y + shape[1] * (z + ..))`, i.e. the
+/// first axis varies fastest like the pixels of a row-major image
+///
+/// # Example
+/// ```
+/// use pulau_rs::grid::{Grid, Neighbourhood};
+///
+/// // 2 voxels that only touch at a corner
+/// let volume = |[x, y, z]: [usize; 3]| x == y && y == z && x < 2;
+/// let mut grid = Grid::<3, 27>::new([3, 3, 3]);
+/// grid.connect(Neighbourhood::Faces, |a, b| volume(a) && volume(b));
+/// assert!(!grid.connected([0, 0, 0], [1, 1, 1]));
+/// grid.connect(Neighbourhood::Corners, |a, b| volume(a) && volume(b));
+/// assert!(grid.connected([0, 0, 0], [1, 1, 1]));
+/// ```
+pub struct Grid<const D: usize, const N: usize> {
+    shape: [usize; D],
+    uf: UnionFind<'static, QuickUnion<BySize>, u32, N>,
+}
+
+impl<const D: usize, const N: usize> Grid<D, N> {
+    /// Creates a grid of singletons
+    ///
+    /// # Panics
+    /// Panics if the product of `shape` is not `N`
+    pub fn new(shape: [usize; D]) -> Self {
+        assert!(
+            shape.iter().product::<usize>() == N,
+            "Grid shape must hold exactly N cells!"
+        );
+
+        Self {
+            shape,
+            uf: UnionFind::<'static, QuickUnion<BySize>, u32, N>::default(),
+        }
+    }
+
+    /// Number of cells along every axis
+    pub fn shape(&self) -> [usize; D] {
+        self.shape
+    }
+
+    /// Index of the cell at `coordinate`
+    ///
+    /// # Panics
+    /// Panics if `coordinate` is outside of the grid
+    pub fn index(&self, coordinate: [usize; D]) -> usize {
+        let mut index = 0;
+        for axis in (0..D).rev() {
+            assert!(
+                coordinate[axis] < self.shape[axis],
+                "Coordinate must be inside of the grid!"
+            );
+            index = index * self.shape[axis] + coordinate[axis];
+        }
+        index
+    }
+
+    /// Coordinate of the cell at `index`, which must be below `N`
+    pub fn coordinate(&self, mut index: usize) -> [usize; D] {
+        let mut coordinate = [0; D];
+        for (at, len) in coordinate.iter_mut().zip(self.shape) {
+            *at = index % len;
+            index /= len;
+        }
+        coordinate
+    }
+
+    fn id(&self, coordinate: [usize; D]) -> u32 {
+        identifier::<u32>(self.index(coordinate))
+    }
+
+    /// Checks whether 2 cells are connected to each other
+    pub fn connected(&mut self, a: [usize; D], b: [usize; D]) -> bool {
+        let (a, b) = (self.id(a), self.id(b));
+        self.uf.connected(a, b)
+    }
+
+    /// Finds the root cell of the component containing `a`
+    pub fn find(&mut self, a: [usize; D]) -> [usize; D] {
+        let a = self.id(a);
+        let root = self.uf.find(a);
+        self.coordinate(root as usize)
+    }
+
+    /// Unions the components of 2 cells, which do not have to be neighbours
+    pub fn union_sets(&mut self, a: [usize; D], b: [usize; D]) -> UnionResult<[usize; D]> {
+        let (a, b) = (self.id(a), self.id(b));
+        match self.uf.union_sets(a, b) {
+            UnionResult::Merged { root, absorbed } => UnionResult::Merged {
+                root: self.coordinate(root as usize),
+                absorbed: self.coordinate(absorbed as usize),
+            },
+            UnionResult::AlreadyConnected { root } => UnionResult::AlreadyConnected {
+                root: self.coordinate(root as usize),
+            },
+        }
+    }
+
+    /// Number of cells in the component containing `a`
+    pub fn set_size(&mut self, a: [usize; D]) -> usize {
+        let a = self.id(a);
+        self.uf.set_size(a)
+    }
+
+    /// Unions every pair of neighbouring cells for which `joins` holds, e.g. voxels that are
+    /// both part of a volume
+    ///
+    /// Every pair is passed once, the earlier cell first. This takes `O(N · 3^D)` unions
+    pub fn connect(
+        &mut self,
+        neighbourhood: Neighbourhood,
+        mut joins: impl FnMut([usize; D], [usize; D]) -> bool,
+    ) {
+        let reach = neighbourhood.reach(D);
+        let offsets = 3usize.pow(D as u32);
+
+        for i in 0..N {
+            let cell = self.coordinate(i);
+
+            // every offset in {-1, 0, 1}^D is a base 3 number, digit 0 stands for -1
+            for code in 0..offsets {
+                let (mut digits, mut changed) = (code, 0);
+                let mut neighbour = cell;
+                let mut inside = true;
+                for (at, len) in neighbour.iter_mut().zip(self.shape) {
+                    let digit = digits % 3;
+                    digits /= 3;
+                    if digit != 1 {
+                        changed += 1;
+                    }
+
+                    match (*at + digit).checked_sub(1) {
+                        Some(moved) if moved < len => *at = moved,
+                        _ => inside = false,
+                    }
+                }
+
+                if inside
+                    && (1..=reach).contains(&changed)
+                    && self.index(neighbour) < i
+                    && joins(neighbour, cell)
+                {
+                    self.union_sets(neighbour, cell);
+                }
+            }
+        }
+    }
+
+    /// Collects the number of cells and the bounding box of every component in a single pass,
+    /// the extent of a component goes to the index of its root while every other entry is
+    /// emptied, i.e. gets no cells
+    ///
+    /// # Panics
+    /// Panics if `out` is shorter than `N`
+    pub fn extents(&mut self, out: &mut [Extent<D>]) {
+        assert!(out.len() >= N, "Extent slice must have at least len >= N!");
+
+        for extent in out.iter_mut() {
+            *extent = Extent {
+                cells: 0,
+                min: self.shape,
+                max: [0; D],
+            };
+        }
+
+        for i in 0..N {
+            let root = self.uf.find(identifier::<u32>(i)) as usize;
+            let cell = self.coordinate(i);
+            let extent = &mut out[root];
+            extent.cells += 1;
+            for ((min, max), at) in extent.min.iter_mut().zip(&mut extent.max).zip(cell) {
+                *min = (*min).min(at);
+                *max = (*max).max(at);
+            }
+        }
+    }
+
+    /// Gets the underlying [`UnionFind`], indexed as described for [`Grid`]
+    pub fn union_find(&mut self) -> &mut UnionFind<'static, QuickUnion<BySize>, u32, N> {
+        &mut self.uf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Extent, Grid, Neighbourhood};
+    use crate::UnionResult;
+
+    #[test]
+    fn test_grid_index() {
+        let grid = Grid::<3, 24>::new([2, 3, 4]);
+        assert_eq!(0, grid.index([0, 0, 0]));
+        assert_eq!(1, grid.index([1, 0, 0]));
+        assert_eq!(2, grid.index([0, 1, 0]));
+        assert_eq!(6, grid.index([0, 0, 1]));
+        assert_eq!(23, grid.index([1, 2, 3]));
+        for i in 0..24 {
+            assert_eq!(i, grid.index(grid.coordinate(i)));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_grid_outside() {
+        Grid::<2, 6>::new([2, 3]).index([2, 0]);
+    }
+
+    #[test]
+    fn test_grid_neighbourhoods() {
+        // a single voxel in the center touches all 26 others
+        let mut counts = [0; 3];
+        for (count, neighbourhood) in counts.iter_mut().zip([
+            Neighbourhood::Faces,
+            Neighbourhood::Edges,
+            Neighbourhood::Corners,
+        ]) {
+            let mut grid = Grid::<3, 27>::new([3, 3, 3]);
+            grid.connect(neighbourhood, |a, b| a == [1; 3] || b == [1; 3]);
+            *count = grid.set_size([1, 1, 1]) - 1;
+        }
+        assert_eq!([6, 18, 26], counts);
+
+        let mut grid = Grid::<2, 9>::new([3, 3]);
+        grid.connect(Neighbourhood::Edges, |a, b| a == [1; 2] || b == [1; 2]);
+        assert_eq!(9, grid.set_size([0, 0]));
+    }
+
+    #[test]
+    fn test_grid_extents() {
+        let mut grid = Grid::<3, 24>::new([4, 3, 2]);
+        assert!(grid.union_sets([0, 0, 0], [1, 0, 0]).is_merged());
+        grid.union_sets([1, 0, 0], [1, 2, 1]);
+        grid.union_sets([3, 1, 0], [3, 1, 1]);
+        assert_eq!(
+            UnionResult::AlreadyConnected { root: [0, 0, 0] },
+            grid.union_sets([1, 2, 1], [0, 0, 0])
+        );
+        assert!(grid.connected([0, 0, 0], [1, 2, 1]));
+
+        let mut extents = [Extent {
+            cells: 0,
+            min: [0; 3],
+            max: [0; 3],
+        }; 24];
+        grid.extents(&mut extents);
+        let root = grid.find([1, 2, 1]);
+        let root = grid.index(root);
+        assert_eq!(
+            Extent {
+                cells: 3,
+                min: [0, 0, 0],
+                max: [1, 2, 1]
+            },
+            extents[root]
+        );
+        let root = grid.find([3, 1, 1]);
+        let root = grid.index(root);
+        assert_eq!(
+            Extent {
+                cells: 2,
+                min: [3, 1, 0],
+                max: [3, 1, 1]
+            },
+            extents[root]
+        );
+        assert_eq!(24 - 3, extents.iter().filter(|e| e.cells > 0).count());
+        assert_eq!(24, extents.iter().map(|e| e.cells).sum::<usize>());
+    }
+}
@@ -0,0 +1,178 @@
+//! Algorithms and vertices whose buffers grow one node at a time
+
+use crate::potential::{Group, Identifier};
+use crate::quickunion::Compression;
+use crate::{
+    AlgorithmContainer, ByRank, BySize, Potential, PotentialVertex, QuickFind, QuickUnionWith, Rem,
+    Rollback, Unweighted, VertexType,
+};
+
+/// Algorithms whose owned buffers can grow one node at a time
+pub trait Grow: AlgorithmContainer {
+    /// Heuristic of a new singleton, `None` for algorithms without a heuristic buffer
+    const SINGLETON: Option<usize>;
+}
+
+impl Grow for QuickFind {
+    const SINGLETON: Option<usize> = None;
+}
+
+impl Grow for Rem {
+    const SINGLETON: Option<usize> = None;
+}
+
+impl<C: Compression> Grow for QuickUnionWith<ByRank, C> {
+    const SINGLETON: Option<usize> = Some(0);
+}
+
+impl<C: Compression> Grow for QuickUnionWith<BySize, C> {
+    const SINGLETON: Option<usize> = Some(1);
+}
+
+impl<C: Compression> Grow for QuickUnionWith<Unweighted, C> {
+    const SINGLETON: Option<usize> = None;
+}
+
+impl<const CAPACITY: usize> Grow for Rollback<ByRank, CAPACITY> {
+    const SINGLETON: Option<usize> = Some(0);
+}
+
+impl<const CAPACITY: usize> Grow for Rollback<BySize, CAPACITY> {
+    const SINGLETON: Option<usize> = Some(1);
+}
+
+impl Grow for Potential<ByRank> {
+    const SINGLETON: Option<usize> = Some(0);
+}
+
+impl Grow for Potential<BySize> {
+    const SINGLETON: Option<usize> = Some(1);
+}
+
+/// Vertices that can be created as a root from their own identifier
+pub trait Singleton: VertexType {
+    fn singleton(id: Self::IdentifierType) -> Self;
+}
+
+macro_rules! generate_singleton_impl {
+    ($($num_type:ident), *) => {
+        $(
+            impl Singleton for $num_type {
+                #[inline(always)]
+                fn singleton(id: Self) -> Self {
+                    id
+                }
+            }
+        )*
+    };
+}
+
+generate_singleton_impl!(u8, u16, u32, u64, usize);
+
+impl<I, W> Singleton for PotentialVertex<I, W>
+where
+    I: Identifier,
+    W: Group,
+{
+    fn singleton(id: I) -> Self {
+        PotentialVertex::new(id)
+    }
+}
+
+/// Forwards every operation of a wrapper that only swaps out the buffers to the wrapped
+/// algorithm `A`
+macro_rules! generate_forwarding_impl {
+    ($($param:ident), * => $wrapper:ty) => {
+        impl<$($param,)* T> $crate::Connected<T> for $wrapper
+        where
+            T: $crate::VertexType,
+            A: $crate::Connected<T>,
+        {
+            fn connected(
+                representative: &mut [T],
+                a: T::IdentifierType,
+                b: T::IdentifierType,
+            ) -> bool {
+                A::connected(representative, a, b)
+            }
+
+            fn try_connected(
+                representative: &mut [T],
+                a: T::IdentifierType,
+                b: T::IdentifierType,
+            ) -> Result<bool, $crate::UnionFindError> {
+                A::try_connected(representative, a, b)
+            }
+        }
+
+        impl<$($param,)* T> $crate::Union<T> for $wrapper
+        where
+            T: $crate::VertexType,
+            A: $crate::Union<T>,
+        {
+            fn union_sets<S: $crate::HeuristicType>(
+                representative: &mut [T],
+                heuristic: &mut [S],
+                a: T::IdentifierType,
+                b: T::IdentifierType,
+            ) -> $crate::UnionResult<T::IdentifierType> {
+                A::union_sets(representative, heuristic, a, b)
+            }
+
+            fn try_union_sets<S: $crate::HeuristicType>(
+                representative: &mut [T],
+                heuristic: &mut [S],
+                a: T::IdentifierType,
+                b: T::IdentifierType,
+            ) -> Result<$crate::UnionResult<T::IdentifierType>, $crate::UnionFindError> {
+                A::try_union_sets(representative, heuristic, a, b)
+            }
+        }
+
+        impl<$($param,)* T> $crate::Find<T> for $wrapper
+        where
+            T: $crate::VertexType,
+            A: $crate::Find<T>,
+        {
+            fn find(representative: &mut [T], a: T::IdentifierType) -> T {
+                A::find(representative, a)
+            }
+
+            fn try_find(
+                representative: &mut [T],
+                a: T::IdentifierType,
+            ) -> Result<T, $crate::UnionFindError> {
+                A::try_find(representative, a)
+            }
+        }
+
+        impl<$($param,)* T> $crate::SetSize<T> for $wrapper
+        where
+            T: $crate::VertexType,
+            A: $crate::SetSize<T>,
+        {
+            fn set_size<S: $crate::HeuristicType>(
+                representative: &mut [T],
+                heuristic: &[S],
+                a: T::IdentifierType,
+            ) -> usize {
+                A::set_size(representative, heuristic, a)
+            }
+        }
+
+        impl<$($param,)* T> $crate::Validate<T> for $wrapper
+        where
+            T: $crate::VertexType,
+            A: $crate::Validate<T>,
+        {
+            fn validate<S: $crate::HeuristicType>(
+                representative: &mut [T],
+                heuristic: &mut [S],
+            ) -> Result<(), $crate::UnionFindError> {
+                A::validate(representative, heuristic)
+            }
+        }
+    };
+}
+
+pub(crate) use generate_forwarding_impl;
@@ -0,0 +1,185 @@
+//! Buffers that grow one node at a time, backed by `Vec`
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::grow::{generate_forwarding_impl, Grow, Singleton};
+use crate::partition::identifier;
+use crate::potential::Weighted;
+use crate::rollback::Reversible;
+use crate::{AlgorithmContainer, QuickUnion, UnionFind, VertexType};
+
+/// [`Growable`] algorithm
+///
+/// Wraps algorithm `A` and keeps its buffers in [`Vec`]s, so nodes are added one at a time with
+/// [`UnionFind::make_set`] instead of fixing their number upfront. Every other operation is
+/// forwarded to `A`, `N` is unused and conventionally `0`
+///
+/// # Example
+/// ```
+/// use pulau_rs::{Growable, QuickUnion, UnionFind};
+///
+/// let mut uf = UnionFind::<Growable<QuickUnion>, u32, 0>::default();
+/// uf.reserve(2);
+/// let a = uf.make_set();
+/// let b = uf.make_set();
+/// assert!(!uf.connected(a, b));
+/// uf.union_sets(a, b);
+/// assert!(uf.connected(a, b));
+/// ```
+#[derive(Debug, Default)]
+pub struct Growable<A = QuickUnion> {
+    algorithm: PhantomData<A>,
+}
+
+impl<A> AlgorithmContainer for Growable<A>
+where
+    A: Grow,
+{
+    type HeuristicContainer<'a, const N: usize> = Vec<usize>;
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = Vec<R>;
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = A::HistoryContainer<'a, R, N>;
+}
+
+impl<'a, A, T, const N: usize> Default for UnionFind<'a, Growable<A>, T, N>
+where
+    T: VertexType + 'a,
+    A: Grow,
+    A::HistoryContainer<'a, T, N>: Default,
+{
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, Growable<A>, T, N>
+where
+    T: VertexType + 'a,
+    A: Grow,
+{
+    /// Creates an empty union find with room for `capacity` sets
+    pub fn with_capacity(capacity: usize) -> Self
+    where
+        A::HistoryContainer<'a, T, N>: Default,
+    {
+        let heuristic = match A::SINGLETON {
+            Some(_) => Vec::with_capacity(capacity),
+            None => Vec::new(),
+        };
+
+        Self {
+            representative: Vec::with_capacity(capacity),
+            heuristic,
+            siblings: [],
+            components: [0; 0],
+            history: Default::default(),
+            algorithm: Default::default(),
+        }
+    }
+
+    /// Reserves room for at least `additional` more sets
+    pub fn reserve(&mut self, additional: usize) {
+        self.representative.reserve(additional);
+        if A::SINGLETON.is_some() {
+            self.heuristic.reserve(additional);
+        }
+    }
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, Growable<A>, T, N>
+where
+    T: Singleton + 'a,
+    T::IdentifierType: TryFrom<usize>,
+    A: Grow,
+{
+    /// Adds a singleton and returns its identifier, which is the number of sets made before
+    ///
+    /// # Panics
+    /// Panics if the identifier does not fit into `T`
+    pub fn make_set(&mut self) -> T::IdentifierType {
+        let id = identifier::<T>(self.representative.len());
+        self.representative.push(T::singleton(id));
+        if let Some(heuristic) = A::SINGLETON {
+            self.heuristic.push(heuristic);
+        }
+        id
+    }
+}
+
+impl<A> Weighted for Growable<A>
+where
+    A: Weighted + Grow,
+{
+    type Heuristic = A::Heuristic;
+}
+
+impl<A> Reversible for Growable<A> where A: Reversible + Grow {}
+
+generate_forwarding_impl!(A => Growable<A>);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BySize, Growable, QuickFind, QuickUnion, QuickUnionWith, UnionFind, UnionFindError,
+        Unweighted,
+    };
+
+    #[test]
+    fn test_make_set() {
+        let mut uf = UnionFind::<Growable<QuickUnion>, u16, 0>::default();
+        assert_eq!(0, uf.count());
+        for i in 0..300 {
+            assert_eq!(i, uf.make_set());
+        }
+        for i in 1..300 {
+            uf.union_sets(i - 1, i);
+        }
+        assert!(uf.connected(0, 299));
+        assert_eq!(1, uf.count());
+        assert_eq!(300, uf.heuristic().len());
+        assert!(uf.validate().is_ok());
+
+        let a = uf.make_set();
+        assert_eq!(2, uf.count());
+        assert_eq!(0, uf.heuristic()[a as usize]);
+        assert_eq!(
+            Err(UnionFindError::OutOfBounds { id: 301, len: 301 }),
+            uf.try_find(301)
+        );
+    }
+
+    #[test]
+    fn test_make_set_heuristics() {
+        let mut uf = UnionFind::<Growable<QuickUnion<BySize>>, u8, 0>::with_capacity(4);
+        let [a, b, c] = [uf.make_set(), uf.make_set(), uf.make_set()];
+        uf.union_sets(a, b);
+        assert_eq!(2, uf.set_size(b));
+        assert_eq!(1, uf.set_size(c));
+
+        let mut uf = UnionFind::<Growable<QuickFind>, u32, 0>::default();
+        let [a, b, c] = [uf.make_set(), uf.make_set(), uf.make_set()];
+        uf.union_sets(a, c);
+        assert!(uf.connected(c, a));
+        assert!(!uf.connected(b, a));
+        assert!(uf.heuristic().is_empty());
+
+        let mut uf = UnionFind::<Growable<QuickUnionWith<Unweighted>>, u64, 0>::default();
+        uf.reserve(10);
+        assert!(uf.representative().capacity() >= 10);
+        assert_eq!(0, uf.heuristic().capacity());
+        let [a, b] = [uf.make_set(), uf.make_set()];
+        assert!(uf.union_sets(a, b).is_merged());
+        assert_eq!(&[1, 1], uf.representative().as_slice());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_make_set_overflow() {
+        let mut uf = UnionFind::<Growable<QuickFind>, u8, 0>::with_capacity(257);
+        for _ in 0..257 {
+            uf.make_set();
+        }
+    }
+}
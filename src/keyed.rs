@@ -0,0 +1,336 @@
+//! Union find over arbitrary keys
+//!
+//! Besides the number of keys `N`, [`KeyedUnionFind`] takes the number of slots of its key
+//! table `SLOTS` as a second const parameter. A default would have to be derived from `N`,
+//! which const generics cannot express yet
+
+use core::hash::{Hash, Hasher};
+
+use crate::{
+    AlgorithmContainer, CapacityError, Connected, Find, Union, UnionFind, UnionResult, VertexType,
+};
+
+/// Marks an empty slot of the key table
+const EMPTY: usize = usize::MAX;
+
+/// 64-bit FNV-1a, which needs no state beyond the hash itself
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// [`UnionFind`] over up to `N` keys of any hashable type, e.g. MAC addresses, string symbols
+/// or coordinates
+///
+/// Keys are interned into the dense indices `0..N` in the order they are first seen, using an
+/// open-addressing table of `SLOTS` slots with linear probing that is stored inline next to the
+/// keys. Unseen keys are inserted by [`KeyedUnionFind::union_sets`] and
+/// [`KeyedUnionFind::find`]
+///
+/// Probes get longer as the table fills up and cost `O(SLOTS)` once it is full, so `SLOTS`
+/// should leave some slack, e.g. `SLOTS >= 2 * N` keeps the load factor at or below one half.
+/// With `SLOTS < N` at most `SLOTS` keys fit
+///
+/// # Example
+/// ```
+/// use pulau_rs::{CapacityError, KeyedUnionFind, QuickUnion};
+///
+/// let mut uf = KeyedUnionFind::<&str, QuickUnion, 3, 6>::default();
+/// uf.union_sets(&"alpha", &"beta").unwrap();
+/// assert!(uf.connected(&"beta", &"alpha"));
+/// assert!(!uf.connected(&"alpha", &"gamma"));
+/// assert_eq!(Ok(&"gamma"), uf.find(&"gamma"));
+/// assert_eq!(Err(CapacityError { capacity: 3 }), uf.find(&"delta"));
+/// ```
+pub struct KeyedUnionFind<K, A, const N: usize, const SLOTS: usize>
+where
+    A: AlgorithmContainer,
+{
+    uf: UnionFind<'static, A, usize, N>,
+    keys: [Option<K>; N],
+    slots: [usize; SLOTS],
+    len: usize,
+}
+
+impl<K, A, const N: usize, const SLOTS: usize> Default for KeyedUnionFind<K, A, N, SLOTS>
+where
+    A: AlgorithmContainer,
+    UnionFind<'static, A, usize, N>: Default,
+{
+    fn default() -> Self {
+        Self {
+            uf: UnionFind::<'static, A, usize, N>::default(),
+            keys: core::array::from_fn(|_| None),
+            slots: [EMPTY; SLOTS],
+            len: 0,
+        }
+    }
+}
+
+impl<K, A, const N: usize, const SLOTS: usize> KeyedUnionFind<K, A, N, SLOTS>
+where
+    K: Hash + Eq,
+    A: AlgorithmContainer,
+{
+    /// Number of keys seen so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no key has been seen so far
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of keys that fit, every key takes one of the `N` indices and one of the `SLOTS`
+    /// slots
+    const CAPACITY: usize = if SLOTS < N { SLOTS } else { N };
+
+    /// Dense index of `key`, or `None` if it has not been seen
+    pub fn index(&self, key: &K) -> Option<usize> {
+        match self.slots[self.probe(key)?] {
+            EMPTY => None,
+            index => Some(index),
+        }
+    }
+
+    /// Key at dense index `index`
+    pub fn key(&self, index: usize) -> Option<&K> {
+        self.keys.get(index)?.as_ref()
+    }
+
+    /// Slot holding `key` or the empty slot where it belongs, `None` if every slot holds
+    /// another key
+    fn probe(&self, key: &K) -> Option<usize> {
+        if SLOTS == 0 {
+            return None;
+        }
+
+        let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+        key.hash(&mut hasher);
+        let start = (hasher.finish() % SLOTS as u64) as usize;
+
+        (0..SLOTS)
+            .map(|step| (start + step) % SLOTS)
+            .find(|&slot| match self.slots[slot] {
+                EMPTY => true,
+                index => self.keys[index].as_ref() == Some(key),
+            })
+    }
+
+    fn key_at(&self, index: usize) -> &K {
+        match &self.keys[index] {
+            Some(key) => key,
+            None => panic!("Index {} does not hold a key!", index),
+        }
+    }
+}
+
+impl<K, A, const N: usize, const SLOTS: usize> KeyedUnionFind<K, A, N, SLOTS>
+where
+    K: Hash + Eq + Clone,
+    A: AlgorithmContainer,
+{
+    /// Dense index of `key`, which is interned first if it has not been seen
+    ///
+    /// # Errors
+    /// Returns [`CapacityError`] if `key` is unseen and all `N` keys or all `SLOTS` slots are in
+    /// use, its capacity is the smaller of the two
+    pub fn insert(&mut self, key: &K) -> Result<usize, CapacityError> {
+        let full = CapacityError {
+            capacity: Self::CAPACITY,
+        };
+        let slot = self.probe(key).ok_or(full)?;
+        if self.slots[slot] != EMPTY {
+            return Ok(self.slots[slot]);
+        }
+        if self.len == Self::CAPACITY {
+            return Err(full);
+        }
+
+        let index = self.len;
+        self.keys[index] = Some(key.clone());
+        self.slots[slot] = index;
+        self.len += 1;
+        Ok(index)
+    }
+}
+
+impl<K, A, const N: usize, const SLOTS: usize> KeyedUnionFind<K, A, N, SLOTS>
+where
+    K: Hash + Eq + Clone,
+    A: AlgorithmContainer + Union<usize> + Find<usize> + Connected<usize>,
+{
+    /// Unions the components of 2 keys, inserting unseen ones
+    ///
+    /// Returns whether the components were merged along with the key of the resulting root
+    ///
+    /// # Errors
+    /// Returns [`CapacityError`] if there is no room for the unseen keys, nothing is inserted or
+    /// unioned in that case
+    pub fn union_sets(&mut self, a: &K, b: &K) -> Result<UnionResult<&K>, CapacityError> {
+        let unseen =
+            usize::from(self.index(a).is_none()) + usize::from(self.index(b).is_none() && a != b);
+        if self.len + unseen > Self::CAPACITY {
+            return Err(CapacityError {
+                capacity: Self::CAPACITY,
+            });
+        }
+
+        let (a, b) = (self.insert(a)?, self.insert(b)?);
+        Ok(match self.uf.union_sets(a, b) {
+            UnionResult::Merged { root, absorbed } => UnionResult::Merged {
+                root: self.key_at(root),
+                absorbed: self.key_at(absorbed),
+            },
+            UnionResult::AlreadyConnected { root } => UnionResult::AlreadyConnected {
+                root: self.key_at(root),
+            },
+        })
+    }
+
+    /// Checks whether 2 keys are connected to each other, an unseen key is only connected to
+    /// itself
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.index(a), self.index(b)) {
+            (Some(a), Some(b)) => self.uf.connected(a, b),
+            _ => a == b,
+        }
+    }
+
+    /// Finds the key at the root of the component containing `a`, inserting `a` if it is
+    /// unseen
+    ///
+    /// # Errors
+    /// Returns [`CapacityError`] if `a` is unseen and there is no room for it
+    pub fn find(&mut self, a: &K) -> Result<&K, CapacityError> {
+        let a = self.insert(a)?;
+        let root = self.uf.find(a).id();
+        Ok(self.key_at(root))
+    }
+
+    /// Number of components among the keys seen so far
+    ///
+    /// This is `O(1)` if the algorithm is wrapped in [`Counted`](crate::Counted), otherwise all
+    /// `N` indices are scanned for roots in `O(N)`
+    pub fn count(&self) -> usize {
+        // every unused index is a singleton of its own
+        self.uf.count() - (N - self.len)
+    }
+
+    /// Gets the underlying [`UnionFind`], indexed by [`KeyedUnionFind::index`]
+    pub fn union_find(&self) -> &UnionFind<'static, A, usize, N> {
+        &self.uf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BySize, CapacityError, Counted, KeyedUnionFind, QuickFind, QuickUnion, UnionResult,
+    };
+
+    #[test]
+    fn test_keyed_mac() {
+        let macs = [
+            [0x00, 0x1b, 0x44, 0x11, 0x3a, 0xb7],
+            [0x00, 0x1b, 0x44, 0x11, 0x3a, 0xb8],
+            [0xde, 0xad, 0xbe, 0xef, 0x00, 0x01],
+            [0x02, 0x42, 0xac, 0x11, 0x00, 0x02],
+        ];
+        let mut uf = KeyedUnionFind::<[u8; 6], QuickUnion<BySize>, 4, 8>::default();
+        assert!(uf.is_empty());
+        assert!(uf.union_sets(&macs[0], &macs[1]).unwrap().is_merged());
+        assert_eq!(
+            UnionResult::Merged {
+                root: &macs[0],
+                absorbed: &macs[2]
+            },
+            uf.union_sets(&macs[2], &macs[0]).unwrap()
+        );
+        assert_eq!(
+            UnionResult::AlreadyConnected { root: &macs[0] },
+            uf.union_sets(&macs[1], &macs[2]).unwrap()
+        );
+        assert_eq!(3, uf.len());
+        assert_eq!(1, uf.count());
+        assert!(!uf.connected(&macs[3], &macs[0]));
+        assert!(uf.connected(&macs[3], &macs[3]));
+        assert_eq!(3, uf.len());
+
+        assert_eq!(Ok(&macs[3]), uf.find(&macs[3]));
+        assert_eq!(2, uf.count());
+        assert_eq!(Some(3), uf.index(&macs[3]));
+        assert_eq!(Some(&macs[2]), uf.key(2));
+        assert_eq!(None, uf.key(4));
+    }
+
+    #[test]
+    fn test_keyed_full() {
+        // every slot is taken, so lookups of unseen keys probe the whole table
+        let mut uf = KeyedUnionFind::<(i32, i32), QuickFind, 3, 3>::default();
+        uf.union_sets(&(0, 0), &(0, 1)).unwrap();
+        uf.union_sets(&(5, -5), &(5, -5)).unwrap();
+        assert_eq!(
+            Err(CapacityError { capacity: 3 }),
+            uf.union_sets(&(0, 0), &(1, 1))
+        );
+        assert_eq!(None, uf.index(&(1, 1)));
+        assert!(!uf.connected(&(1, 1), &(0, 0)));
+        assert!(uf.connected(&(0, 1), &(0, 0)));
+        assert_eq!(Ok(&(5, -5)), uf.find(&(5, -5)));
+        assert_eq!(2, uf.count());
+
+        // free slots are left over once all `N` keys are in use
+        let mut uf = KeyedUnionFind::<u16, Counted<QuickUnion>, 2, 4>::default();
+        uf.union_sets(&300, &7).unwrap();
+        assert_eq!(Err(CapacityError { capacity: 2 }), uf.find(&8));
+        assert_eq!(None, uf.index(&8));
+        assert_eq!(1, uf.count());
+
+        // fewer slots than indices, a failed union inserts neither key
+        let mut uf = KeyedUnionFind::<char, QuickUnion, 4, 3>::default();
+        uf.union_sets(&'a', &'b').unwrap();
+        assert_eq!(
+            Err(CapacityError { capacity: 3 }),
+            uf.union_sets(&'c', &'d')
+        );
+        assert_eq!(None, uf.index(&'c'));
+        assert_eq!(2, uf.len());
+        assert!(uf.union_sets(&'c', &'c').is_ok());
+        assert_eq!(Err(CapacityError { capacity: 3 }), uf.find(&'d'));
+        assert_eq!(2, uf.count());
+
+        let mut uf = KeyedUnionFind::<u8, QuickUnion, 0, 0>::default();
+        assert_eq!(Err(CapacityError { capacity: 0 }), uf.find(&1));
+        assert_eq!(0, uf.count());
+    }
+
+    #[test]
+    fn test_keyed_symbols() {
+        let words = [
+            "the", "quick", "brown", "fox", "jumps", "over", "lazy", "dog",
+        ];
+        let mut uf = KeyedUnionFind::<&str, QuickUnion, 8, 16>::default();
+        for pair in words.chunks(2) {
+            uf.union_sets(&pair[0], &pair[1]).unwrap();
+        }
+        uf.union_sets(&"fox", &"the").unwrap();
+        for (i, word) in words.iter().enumerate() {
+            assert_eq!(Some(i), uf.index(word));
+        }
+        assert!(uf.connected(&"quick", &"brown"));
+        assert!(!uf.connected(&"lazy", &"jumps"));
+        assert_eq!(3, uf.count());
+        assert_eq!(uf.find(&"quick").copied(), uf.find(&"fox").copied());
+    }
+}
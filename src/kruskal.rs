@@ -0,0 +1,131 @@
+//! Minimum spanning forest using Kruskal's algorithm
+
+use crate::potential::Identifier;
+use crate::{QuickUnion, UnionFind};
+
+/// Outcome of [`kruskal`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpanningForest<W> {
+    /// Sum of the weights of the chosen edges
+    pub weight: W,
+    /// Number of chosen edges, which are at the front of the output buffer
+    pub edges: usize,
+    /// Number of trees in the forest, `1` if the graph is connected
+    pub trees: usize,
+}
+
+/// Finds a minimum spanning forest of the graph on `N` nodes made of `edges`
+///
+/// `edges` is sorted by weight in place, the chosen edges are written to the front of `out` in
+/// ascending order of weight. Runs in `O(E lg E)` without allocating
+///
+/// Weights must be totally ordered, floating point weights can be scaled to integers first
+///
+/// # Panics
+/// Panics if `out` is shorter than `N - 1` or if an edge refers to a node that is not below `N`
+///
+/// # Example
+/// ```
+/// use pulau_rs::kruskal::{kruskal, SpanningForest};
+///
+/// let mut edges = [(0u8, 1, 4), (1, 2, 1), (0, 2, 2), (3, 4, 7)];
+/// let mut tree = [(0, 0, 0); 4];
+/// let forest = kruskal::<u8, u32, 5>(&mut edges, &mut tree);
+/// assert_eq!(SpanningForest { weight: 10, edges: 3, trees: 2 }, forest);
+/// assert_eq!([(1, 2, 1), (0, 2, 2), (3, 4, 7)], tree[..forest.edges]);
+/// ```
+pub fn kruskal<I, W, const N: usize>(
+    edges: &mut [(I, I, W)],
+    out: &mut [(I, I, W)],
+) -> SpanningForest<W>
+where
+    I: Identifier + 'static,
+    W: Copy + Ord + Default + core::ops::AddAssign,
+    UnionFind<'static, QuickUnion, I, N>: Default,
+{
+    let needed = N.saturating_sub(1);
+    assert!(
+        out.len() >= needed,
+        "Output slice must have at least len >= N - 1!"
+    );
+
+    edges.sort_unstable_by_key(|edge| edge.2);
+
+    let mut uf = UnionFind::<'static, QuickUnion, I, N>::default();
+    let mut forest = SpanningForest {
+        weight: W::default(),
+        edges: 0,
+        trees: N,
+    };
+
+    for &(a, b, weight) in edges.iter() {
+        if forest.edges == needed {
+            break;
+        }
+
+        if uf.union_sets(a, b).is_merged() {
+            out[forest.edges] = (a, b, weight);
+            forest.weight += weight;
+            forest.edges += 1;
+        }
+    }
+
+    forest.trees -= forest.edges;
+    forest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{kruskal, SpanningForest};
+
+    #[test]
+    fn test_kruskal() {
+        let mut edges = [
+            (0u16, 1, 7i32),
+            (0, 3, 5),
+            (1, 2, 8),
+            (1, 3, 9),
+            (1, 4, 7),
+            (2, 4, 5),
+            (3, 4, 15),
+            (3, 5, 6),
+            (4, 5, 8),
+            (4, 6, 9),
+            (5, 6, 11),
+        ];
+        let mut tree = [(0, 0, 0); 6];
+        let forest = kruskal::<u16, i32, 7>(&mut edges, &mut tree);
+        assert_eq!(
+            SpanningForest {
+                weight: 39,
+                edges: 6,
+                trees: 1
+            },
+            forest
+        );
+
+        let mut chosen = tree.map(|(a, b, _)| (a.min(b), a.max(b)));
+        chosen.sort_unstable();
+        assert_eq!([(0, 1), (0, 3), (1, 4), (2, 4), (3, 5), (4, 6)], chosen);
+        assert!(edges.windows(2).all(|w| w[0].2 <= w[1].2));
+    }
+
+    #[test]
+    fn test_kruskal_forest() {
+        let mut edges = [(2u32, 3, 1u64), (3, 2, 0), (2, 2, 0), (0, 1, 4)];
+        let mut tree = [(0, 0, 0); 4];
+        let forest = kruskal::<u32, u64, 5>(&mut edges, &mut tree);
+        assert_eq!(
+            SpanningForest {
+                weight: 4,
+                edges: 2,
+                trees: 3
+            },
+            forest
+        );
+        assert_eq!([(3, 2, 0), (0, 1, 4)], tree[..2]);
+
+        let forest = kruskal::<u8, u8, 0>(&mut [], &mut []);
+        assert_eq!(0, forest.trees);
+    }
+}
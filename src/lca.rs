@@ -0,0 +1,278 @@
+//! Offline lowest common ancestors using Tarjan's algorithm
+
+use crate::partition::identifier;
+use crate::{QuickUnion, UnionFind, VertexType};
+
+/// Marks a missing child or a node that is not reachable from a root
+const NONE: usize = usize::MAX;
+
+/// Answers a batch of lowest common ancestor queries on a forest given as a parent array, where
+/// every root is its own parent just like in a representative buffer
+///
+/// The answer to `queries[k]` is written to `out[k]`, `None` if the 2 nodes are in different
+/// trees or one of them is not reachable from any root. `scratch` links the queries that are due
+/// at the same node, its contents are overwritten. Trees are walked iteratively with `O(N)`
+/// stack buffers, so the whole batch takes `O((N + Q) α(N))` without recursion or allocation
+///
+/// # Panics
+/// Panics if `parents` is shorter than `N`, `out` or `scratch` is shorter than `queries`, or a
+/// parent or a query refers to a node that is not below `N`
+///
+/// # Example
+/// ```
+/// use pulau_rs::lca::lca;
+///
+/// //     0
+/// //    / \
+/// //   1   2
+/// //  / \
+/// // 3   4
+/// let parents = [0u8, 0, 0, 1, 1];
+/// let queries = [(3, 4), (4, 2)];
+/// let mut out = [None; 2];
+/// lca::<u8, 5>(&parents, &queries, &mut out, &mut [0; 2]);
+/// assert_eq!([Some(1), Some(0)], out);
+/// ```
+pub fn lca<I, const N: usize>(
+    parents: &[I],
+    queries: &[(I::IdentifierType, I::IdentifierType)],
+    out: &mut [Option<I::IdentifierType>],
+    scratch: &mut [usize],
+) where
+    I: VertexType,
+    I::IdentifierType: TryFrom<usize>,
+{
+    assert!(
+        parents.len() >= N,
+        "Parent slice must have at least len >= N!"
+    );
+
+    // children as linked lists, built backwards so that they are visited in ascending order
+    let mut first_child = [NONE; N];
+    let mut next_sibling = [NONE; N];
+    for i in (0..N).rev() {
+        let parent = I::usize(parents[i].id());
+        if parent != i {
+            next_sibling[i] = first_child[parent];
+            first_child[parent] = i;
+        }
+    }
+
+    tarjan::<I, N, _>(
+        || (0..N).filter(|&i| I::usize(parents[i].id()) == i),
+        |u| first_child[u],
+        |_, cursor| (cursor != NONE).then(|| (cursor, next_sibling[cursor])),
+        queries,
+        out,
+        scratch,
+    );
+}
+
+/// Same as [`lca`] for a single tree given as the children of every node, `children[i]` lists
+/// the children of node `i`
+///
+/// # Panics
+/// Panics if `children` is shorter than `N`, `out` or `scratch` is shorter than `queries`, a
+/// node is not below `N`, or a node is reached twice from `root`, i.e. `children` does not
+/// describe a tree
+pub fn lca_children<I, const N: usize>(
+    children: &[&[I]],
+    root: I,
+    queries: &[(I::IdentifierType, I::IdentifierType)],
+    out: &mut [Option<I::IdentifierType>],
+    scratch: &mut [usize],
+) where
+    I: VertexType,
+    I::IdentifierType: TryFrom<usize>,
+{
+    assert!(
+        children.len() >= N,
+        "Children slice must have at least len >= N!"
+    );
+
+    tarjan::<I, N, _>(
+        || core::iter::once(I::usize(root.id())),
+        |_| 0,
+        |u, cursor| {
+            children[u]
+                .get(cursor)
+                .map(|c| (I::usize(c.id()), cursor + 1))
+        },
+        queries,
+        out,
+        scratch,
+    );
+}
+
+/// Walks every tree in post-order, `start` and `child` iterate the children of a node through a
+/// cursor. `finish` gets the node, its parent, its post-order time and the time at which its
+/// tree started
+///
+/// # Panics
+/// Panics if a node is reached twice
+fn post_order<const N: usize>(
+    roots: impl Iterator<Item = usize>,
+    start: &impl Fn(usize) -> usize,
+    child: &impl Fn(usize, usize) -> Option<(usize, usize)>,
+    mut finish: impl FnMut(usize, Option<usize>, usize, usize),
+) {
+    let mut stack = [0; N];
+    let mut cursor = [0; N];
+    let mut seen = [false; N];
+    let mut time = 0;
+
+    for root in roots {
+        let tree_start = time;
+        seen[root] = true;
+        stack[0] = root;
+        cursor[root] = start(root);
+        let mut len = 1;
+
+        while len > 0 {
+            let u = stack[len - 1];
+            match child(u, cursor[u]) {
+                Some((c, next)) => {
+                    assert!(!seen[c], "Children must describe a tree!");
+                    seen[c] = true;
+                    cursor[u] = next;
+                    cursor[c] = start(c);
+                    stack[len] = c;
+                    len += 1;
+                }
+                None => {
+                    len -= 1;
+                    let parent = len.checked_sub(1).map(|top| stack[top]);
+                    finish(u, parent, time, tree_start);
+                    time += 1;
+                }
+            }
+        }
+    }
+}
+
+fn tarjan<I, const N: usize, R>(
+    roots: impl Fn() -> R,
+    start: impl Fn(usize) -> usize,
+    child: impl Fn(usize, usize) -> Option<(usize, usize)>,
+    queries: &[(I::IdentifierType, I::IdentifierType)],
+    out: &mut [Option<I::IdentifierType>],
+    scratch: &mut [usize],
+) where
+    I: VertexType,
+    I::IdentifierType: TryFrom<usize>,
+    R: Iterator<Item = usize>,
+{
+    assert!(
+        out.len() >= queries.len(),
+        "Output slice must have at least len >= number of queries!"
+    );
+    assert!(
+        scratch.len() >= queries.len(),
+        "Scratch slice must have at least len >= number of queries!"
+    );
+
+    let mut post = [NONE; N];
+    post_order::<N>(roots(), &start, &child, |u, _, time, _| post[u] = time);
+
+    // a query is answered once both of its nodes are done, i.e. at the one finished later,
+    // queries due at the same node are linked through `scratch` in ascending order
+    let mut due = [NONE; N];
+    for (k, &(a, b)) in queries.iter().enumerate().rev() {
+        let (a, b) = (I::usize(a), I::usize(b));
+        // a node that is not reachable from any root is never done
+        let u = if post[a] >= post[b] { a } else { b };
+        scratch[k] = due[u];
+        due[u] = k;
+    }
+    out[..queries.len()].fill(None);
+
+    // nodes are tracked by index, `I` only shows up in the queries and the answers
+    let mut uf = UnionFind::<QuickUnion, usize, N>::default();
+    let mut ancestor = [0; N];
+    post_order::<N>(roots(), &start, &child, |u, parent, _, tree_start| {
+        ancestor[uf.find(u)] = u;
+
+        let mut k = due[u];
+        while k != NONE {
+            let (a, b) = queries[k];
+            let other = if I::usize(a) == u {
+                I::usize(b)
+            } else {
+                I::usize(a)
+            };
+            // nodes of earlier trees are done as well but share no ancestor with `u`
+            if post[other] >= tree_start {
+                out[k] = Some(identifier::<I>(ancestor[uf.find(other)]));
+            }
+            k = scratch[k];
+        }
+
+        if let Some(parent) = parent {
+            uf.union_sets(u, parent);
+            ancestor[uf.find(parent)] = parent;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lca, lca_children};
+
+    //       0
+    //     / | \
+    //    1  2  3
+    //   / \     \
+    //  4   5     6
+    //      |
+    //      7
+    const PARENTS: [u16; 8] = [0, 0, 0, 0, 1, 1, 3, 5];
+    const EXPECTED: [((u16, u16), u16); 6] = [
+        ((4, 7), 1),
+        ((7, 6), 0),
+        ((2, 2), 2),
+        ((5, 7), 5),
+        ((4, 1), 1),
+        ((6, 3), 3),
+    ];
+
+    fn check(out: &[Option<u16>]) {
+        for ((query, expected), answer) in EXPECTED.iter().zip(out) {
+            assert_eq!(Some(*expected), *answer, "{:?}", query);
+        }
+    }
+
+    #[test]
+    fn test_lca() {
+        let queries = EXPECTED.map(|(query, _)| query);
+        let mut out = [None; 6];
+        lca::<u16, 8>(&PARENTS, &queries, &mut out, &mut [0; 6]);
+        check(&out);
+    }
+
+    #[test]
+    fn test_lca_children() {
+        let children: [&[u16]; 8] = [&[1, 2, 3], &[4, 5], &[], &[6], &[], &[7], &[], &[]];
+        let queries = EXPECTED.map(|(query, _)| query);
+        let mut out = [Some(42); 7];
+        lca_children::<u16, 8>(&children, 0, &queries, &mut out, &mut [0; 6]);
+        check(&out[..6]);
+        assert_eq!(Some(42), out[6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_lca_children_diamond() {
+        let children: [&[u8]; 4] = [&[1, 2], &[3], &[3], &[]];
+        lca_children::<u8, 4>(&children, 0, &[(1, 2)], &mut [None], &mut [0]);
+    }
+
+    #[test]
+    fn test_lca_forest() {
+        // 2 trees and a cycle that is not reachable from any root
+        let parents = [0u8, 0, 2, 2, 5, 4];
+        let queries = [(1, 3), (3, 2), (1, 1), (4, 0), (5, 4), (2, 3)];
+        let mut out = [Some(0); 6];
+        lca::<u8, 6>(&parents, &queries, &mut out, &mut [0; 6]);
+        assert_eq!([None, Some(2), Some(1), None, None, Some(2)], out);
+    }
+}
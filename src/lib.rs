@@ -9,7 +9,7 @@
     clippy::dbg_macro,
     clippy::debug_assert_with_mut_call,
     clippy::doc_markdown,
-    clippy::empty_enum,
+    clippy::empty_enums,
     clippy::enum_glob_use,
     clippy::exit,
     clippy::expl_impl_clone_on_copy,
@@ -30,11 +30,9 @@
     clippy::macro_use_imports,
     clippy::manual_ok_or,
     clippy::map_flatten,
-    clippy::match_on_vec_items,
     clippy::match_same_arms,
     clippy::match_wildcard_for_single_variants,
     clippy::mem_forget,
-    clippy::mismatched_target_os,
     clippy::missing_errors_doc,
     clippy::missing_safety_doc,
     clippy::mut_mut,
@@ -51,7 +49,6 @@
     clippy::string_add_assign,
     clippy::string_add,
     clippy::string_lit_as_bytes,
-    clippy::string_to_string,
     clippy::todo,
     clippy::trait_duplication_in_bounds,
     clippy::unimplemented,
@@ -66,15 +63,67 @@
 )]
 #![doc = include_str!("../libdoc.md")]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod bipartite;
+#[cfg(feature = "heapless")]
+pub mod bounded;
+pub mod ccl;
+#[cfg(all(target_has_atomic = "32", target_has_atomic = "ptr"))]
+pub mod concurrent;
+pub mod counted;
+pub mod cycle;
+pub mod export;
+pub mod grid;
+pub mod grow;
+#[cfg(feature = "alloc")]
+pub mod growable;
+pub mod keyed;
+pub mod kruskal;
+pub mod lca;
+pub mod linked;
+pub mod mapped;
+pub mod partition;
+pub mod potential;
 pub mod quickfind;
 pub mod quickunion;
-
+pub mod rem;
+pub mod rollback;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "critical-section")]
+pub mod shared;
+pub mod snapshot;
+
+use core::fmt;
 use core::marker::PhantomData;
 use core::ops::AddAssign;
 
+pub use crate::bipartite::{Bipartite, OddCycle};
+#[cfg(feature = "heapless")]
+pub use crate::bounded::Bounded;
+#[cfg(all(target_has_atomic = "32", target_has_atomic = "ptr"))]
+pub use crate::concurrent::AtomicUnionFind;
+pub use crate::counted::Counted;
+#[cfg(feature = "alloc")]
+pub use crate::growable::Growable;
+pub use crate::keyed::KeyedUnionFind;
+pub use crate::linked::Linked;
+pub use crate::mapped::{Mapped, MappedUnionFind};
+pub use crate::potential::{Contradiction, Group, Potential, PotentialVertex};
 pub use crate::quickfind::QuickFind;
-pub use crate::quickunion::QuickUnion;
 pub use crate::quickunion::{ByRank, BySize, Unweighted};
+pub use crate::quickunion::{FullCompression, NoCompression, PathHalving, PathSplitting};
+pub use crate::quickunion::{QuickUnion, QuickUnionWith};
+pub use crate::rem::Rem;
+pub use crate::rollback::{Checkpoint, Rollback};
+#[cfg(feature = "critical-section")]
+pub use crate::shared::SharedUnionFind;
+pub use crate::snapshot::SnapshotError;
+
+use crate::quickunion::Compression;
+use crate::rollback::History;
 
 /// Any type that can be used to index internal buffer
 pub trait VertexType: Eq + Copy {
@@ -106,6 +155,134 @@ macro_rules! generate_index_type_impl{
 
 generate_index_type_impl!(u8, u16, u32, u64, usize);
 
+/// Value in a heuristic buffer, a plain [`usize`] or a mapped [`Word`](crate::mapped::Word)
+pub trait HeuristicType: Copy {
+    fn get(&self) -> usize;
+    /// Stores `value`, which must fit into the heuristic type
+    fn set(&mut self, value: usize);
+}
+
+impl HeuristicType for usize {
+    #[inline(always)]
+    fn get(&self) -> usize {
+        *self
+    }
+
+    #[inline(always)]
+    fn set(&mut self, value: usize) {
+        *self = value;
+    }
+}
+
+/// Contiguous container of [`HeuristicType`] values
+pub trait HeuristicBuffer:
+    AsRef<[<Self as HeuristicBuffer>::Value]> + AsMut<[<Self as HeuristicBuffer>::Value]>
+{
+    type Value: HeuristicType;
+}
+
+impl<S: HeuristicType, const N: usize> HeuristicBuffer for [S; N] {
+    type Value = S;
+}
+
+impl<S: HeuristicType> HeuristicBuffer for &mut [S] {
+    type Value = S;
+}
+
+#[cfg(feature = "alloc")]
+impl<S: HeuristicType> HeuristicBuffer for alloc::vec::Vec<S> {
+    type Value = S;
+}
+
+#[cfg(any(test, feature = "heapless"))]
+impl<S: HeuristicType, const N: usize> HeuristicBuffer for heapless::Vec<S, N> {
+    type Value = S;
+}
+
+/// Outcome of a union operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnionResult<I> {
+    /// 2 different components were merged, `absorbed` is the old root that now hangs under `root`
+    Merged { root: I, absorbed: I },
+    /// Both nodes were already part of the component rooted at `root`
+    AlreadyConnected { root: I },
+}
+
+impl<I: Copy> UnionResult<I> {
+    /// Root of the component containing both nodes after the union
+    pub fn root(&self) -> I {
+        match *self {
+            Self::Merged { root, .. } | Self::AlreadyConnected { root } => root,
+        }
+    }
+
+    /// Whether the union merged 2 different components
+    pub fn is_merged(&self) -> bool {
+        matches!(self, Self::Merged { .. })
+    }
+}
+
+/// Errors returned by the fallible `try_*` operations of [`UnionFind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnionFindError {
+    /// `id` does not index into the representative buffer of length `len`
+    OutOfBounds { id: usize, len: usize },
+    /// The parent chain starting at `id` leaves the representative buffer or never reaches a root
+    CorruptedParent { id: usize },
+    /// Root `id` does not index into the heuristic buffer of length `len`
+    HeuristicOutOfBounds { id: usize, len: usize },
+    /// The history already holds `capacity` unions
+    HistoryFull { capacity: usize },
+    /// The heuristic of `id` does not match the shape of the forest
+    InconsistentHeuristic { id: usize },
+    /// A byte buffer of length `len` does not hold whole parent and heuristic arrays
+    InvalidLayout { len: usize },
+    /// A union recorded before the checkpoint was taken has been undone since
+    StaleCheckpoint,
+}
+
+impl fmt::Display for UnionFindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { id, len } => {
+                write!(f, "id {} is out of bounds for length {}", id, len)
+            }
+            Self::CorruptedParent { id } => write!(f, "parent chain of {} is corrupted", id),
+            Self::HeuristicOutOfBounds { id, len } => {
+                write!(
+                    f,
+                    "root {} is out of bounds for heuristic of length {}",
+                    id, len
+                )
+            }
+            Self::HistoryFull { capacity } => {
+                write!(f, "history is full with {} unions", capacity)
+            }
+            Self::InconsistentHeuristic { id } => {
+                write!(f, "heuristic of {} is inconsistent", id)
+            }
+            Self::InvalidLayout { len } => {
+                write!(f, "byte buffer of length {} does not fit the layout", len)
+            }
+            Self::StaleCheckpoint => f.write_str("checkpoint refers to unions that were undone"),
+        }
+    }
+}
+
+/// Returned when a fixed capacity container has no room for another node, e.g. by
+/// [`KeyedUnionFind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    /// Number of nodes that fit, at most `N`
+    pub capacity: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "capacity of {} nodes is exhausted", self.capacity)
+    }
+}
+
 /// [`UnionFind`] data structure
 ///
 /// This data structure stores a collection of disjoint (non-overlapping) sets.
@@ -136,9 +313,14 @@ generate_index_type_impl!(u8, u16, u32, u64, usize);
 /// If it's weighted then, size of [`UnionFind`] is `T * N + size_of(usize) * N`
 ///
 /// Else it will be `T * N`
-/// 
+///
 /// If you are using borrowed buffers, then the size will be the `core::mem::size_of::<usize>() * 2`
 /// if it's weighted, else it will just be `core::mem::size_of::<usize>()`
+///
+/// Wrapping the algorithm in [`Counted`] adds another `core::mem::size_of::<usize>()`, wrapping it in
+/// [`Linked`] adds `T::IdentifierType * N` or `core::mem::size_of::<usize>() * 2` for borrowed buffers
+///
+/// [`Rollback`] adds its history stack of `CAPACITY` entries, its length and its epoch
 pub struct UnionFind<'a, A, T, const N: usize>
 where
     T: VertexType + 'a,
@@ -146,6 +328,9 @@ where
 {
     representative: A::RepresentativeContainer<'a, T, N>,
     heuristic: A::HeuristicContainer<'a, N>,
+    siblings: A::SiblingContainer<'a, T, N>,
+    components: A::ComponentContainer,
+    history: A::HistoryContainer<'a, T, N>,
     algorithm: PhantomData<A>,
 }
 
@@ -166,8 +351,86 @@ where
 
     /// Unions 2 node. If those 2 nodes are already part of the same component
     /// then this does nothing
-    pub fn union_sets(&mut self, a: T::IdentifierType, b: T::IdentifierType) {
-        A::union_sets(self.representative.as_mut(), self.heuristic.as_mut(), a, b)
+    ///
+    /// Returns whether the components were merged along with the root of the resulting component
+    ///
+    /// # Panics
+    /// Panics if either node is out of bounds, or if `A` records its unions like [`Rollback`] and
+    /// its history is full. Use [`UnionFind::try_union_sets`] to get [`UnionFindError`] instead
+    pub fn union_sets(
+        &mut self,
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> UnionResult<T::IdentifierType> {
+        if <A::HistoryContainer<'a, T, N> as History<T>>::RECORDS {
+            let representative = self.representative.as_mut();
+            let roots = [A::find(representative, a), A::find(representative, b)];
+            return match self.union_recorded(roots) {
+                Ok(result) => result,
+                Err(err) => panic!("{}", err),
+            };
+        }
+
+        let result = A::union_sets(self.representative.as_mut(), self.heuristic.as_mut(), a, b);
+        self.record(&result);
+        result
+    }
+
+    /// Checks whether 2 nodes are connected to each other without panicking
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError`] if either node is out of bounds or the buffers are corrupted
+    pub fn try_connected(
+        &mut self,
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<bool, UnionFindError> {
+        A::try_connected(self.representative.as_mut(), a, b)
+    }
+
+    /// Finds a node without panicking
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError`] if the node is out of bounds or the buffers are corrupted
+    pub fn try_find(&mut self, a: T::IdentifierType) -> Result<T, UnionFindError> {
+        A::try_find(self.representative.as_mut(), a)
+    }
+
+    /// Unions 2 node without panicking. If those 2 nodes are already part of the same component
+    /// then this does nothing
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError`] if either node is out of bounds or the buffers are corrupted.
+    /// No union is performed if an error is returned
+    pub fn try_union_sets(
+        &mut self,
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<UnionResult<T::IdentifierType>, UnionFindError> {
+        if <A::HistoryContainer<'a, T, N> as History<T>>::RECORDS {
+            let representative = self.representative.as_mut();
+            let roots = [
+                A::try_find(representative, a)?,
+                A::try_find(representative, b)?,
+            ];
+            return self.union_recorded(roots);
+        }
+
+        let result =
+            A::try_union_sets(self.representative.as_mut(), self.heuristic.as_mut(), a, b)?;
+        self.record(&result);
+        Ok(result)
+    }
+
+    /// Number of components
+    ///
+    /// This is `O(1)` if the algorithm is wrapped in [`Counted`], otherwise every node is
+    /// scanned for roots in `O(N)`
+    pub fn count(&self) -> usize {
+        match self.components.as_ref().first() {
+            Some(&count) => count,
+            None => count_roots(self.representative.as_ref()),
+        }
     }
 
     /// Gets the representative slice
@@ -181,22 +444,138 @@ where
     }
 }
 
+impl<'a, A, T, const N: usize> UnionFind<'a, A, T, N>
+where
+    T: VertexType,
+    A: AlgorithmContainer,
+{
+    /// Updates the bookkeeping after a union
+    fn record(&mut self, result: &UnionResult<T::IdentifierType>) {
+        if let UnionResult::Merged { root, absorbed } = *result {
+            if let Some(count) = self.components.as_mut().first_mut() {
+                *count -= 1;
+            }
+
+            // swapping the successors of 2 nodes in different rings splices them into one
+            let siblings = self.siblings.as_mut();
+            if !siblings.is_empty() {
+                siblings.swap(T::usize(root), T::usize(absorbed));
+            }
+        }
+    }
+
+    /// Reverts the bookkeeping of a union that has been undone
+    fn unrecord(&mut self, result: &UnionResult<T::IdentifierType>) {
+        if let UnionResult::Merged { root, absorbed } = *result {
+            if let Some(count) = self.components.as_mut().first_mut() {
+                *count += 1;
+            }
+
+            // swapping the same successors again splits the rings apart
+            let siblings = self.siblings.as_mut();
+            if !siblings.is_empty() {
+                siblings.swap(T::usize(root), T::usize(absorbed));
+            }
+        }
+    }
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, A, T, N>
+where
+    T: VertexType,
+    A: AlgorithmContainer + Validate<T> + Find<T>,
+{
+    /// Checks that the representative and heuristic buffers are well-formed for `A`,
+    /// which is useful for buffers handed to the borrowed-slice constructors
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError`] describing the first node that breaks an invariant of `A`
+    pub fn validate(&mut self) -> Result<(), UnionFindError> {
+        A::validate(self.representative.as_mut(), self.heuristic.as_mut())
+    }
+
+    /// Validates freshly loaded buffers and rebuilds the component count and sibling rings,
+    /// the sibling rings must still be in their initial state
+    pub(crate) fn restore(&mut self) -> Result<(), UnionFindError> {
+        self.validate()?;
+
+        if let Some(count) = self.components.as_mut().first_mut() {
+            *count = count_roots(self.representative.as_ref());
+        }
+
+        let siblings = self.siblings.as_mut();
+        if !siblings.is_empty() {
+            linked::link::<A, T>(self.representative.as_mut(), siblings);
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, A, T, N>
+where
+    T: VertexType,
+    A: AlgorithmContainer + SetSize<T>,
+{
+    /// Number of nodes in the component containing `a`
+    ///
+    /// This is `O(1)` for [`BySize`], other algorithms fall back to scanning every node in `O(N)`
+    pub fn set_size(&mut self, a: T::IdentifierType) -> usize {
+        A::set_size(self.representative.as_mut(), self.heuristic.as_ref(), a)
+    }
+}
+
 /// This trait represents the kind of containers that is required for a particular algorithm to function
 pub trait AlgorithmContainer {
     /// Any kind of contiguous container
-    /// 
+    ///
     /// # Examples
     /// - `[T; N]`
     /// - `[T; 0]`
     /// - `heapless::Vec<T, N>`
-    type HeuristicContainer<'a, const N: usize>: AsRef<[usize]> + AsMut<[usize]>;
+    /// - `&'a mut [[u8; 4]]`
+    type HeuristicContainer<'a, const N: usize>: HeuristicBuffer;
 
     /// Any kind of contiguous container (should not be ZST). `R` must also live as long as `'a`
-    /// 
+    ///
     /// # Examples
     /// - `[T; N]`
     /// - `heaples::Vec<T, N>`
     type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize>: AsRef<[R]> + AsMut<[R]>;
+
+    /// Links every node to the next member of its component in a circular list,
+    /// any ZST container opts out
+    ///
+    /// # Examples
+    /// - `[R::IdentifierType; N]`
+    /// - `[R::IdentifierType; 0]`
+    /// - `&'a mut [R::IdentifierType]`
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize>: AsRef<[R::IdentifierType]>
+        + AsMut<[R::IdentifierType]>;
+
+    /// Holds the number of components, `[usize; 1]` to maintain it or `[usize; 0]` to opt out
+    ///
+    /// # Examples
+    /// - `[usize; 0]`
+    /// - `[usize; 1]`
+    type ComponentContainer: AsRef<[usize]> + AsMut<[usize]>;
+
+    /// Records what every union overwrites so that it can be undone, `()` to opt out
+    ///
+    /// # Examples
+    /// - `()`
+    /// - `Stack<[Option<Undo<R>>; CAPACITY]>`
+    /// - `Stack<&'a mut [Option<Undo<R>>]>`
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize>: History<R>;
+}
+
+/// Counts the nodes that are their own parent
+fn count_roots<T: VertexType>(representative: &[T]) -> usize {
+    representative
+        .iter()
+        .enumerate()
+        .filter(|(i, node)| T::usize(node.id()) == *i)
+        .count()
 }
 
 /// Union operation
@@ -204,12 +583,23 @@ pub trait Union<T>
 where
     T: VertexType,
 {
-    fn union_sets(
+    fn union_sets<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> UnionResult<T::IdentifierType>;
+
+    /// Fallible version of [`Union::union_sets`]
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError`] if either node is out of bounds or the buffers are corrupted
+    fn try_union_sets<S: HeuristicType>(
         representative: &mut [T],
-        heuristic: &mut [usize],
+        heuristic: &mut [S],
         a: T::IdentifierType,
         b: T::IdentifierType,
-    );
+    ) -> Result<UnionResult<T::IdentifierType>, UnionFindError>;
 }
 
 /// Find operation
@@ -218,6 +608,76 @@ where
     T: VertexType,
 {
     fn find(representative: &mut [T], a: T::IdentifierType) -> T;
+
+    /// Fallible version of [`Find::find`]
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError`] if the node is out of bounds or the buffer is corrupted
+    fn try_find(representative: &mut [T], a: T::IdentifierType) -> Result<T, UnionFindError>;
+}
+
+/// Set size operation
+pub trait SetSize<T>
+where
+    T: VertexType,
+{
+    fn set_size<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &[S],
+        a: T::IdentifierType,
+    ) -> usize;
+}
+
+/// Counts the nodes sharing a root with `a` by running `find` on every node
+///
+/// The parent of a node always lives in the same component, so it stands in for the node itself
+fn scan_set_size<A, T>(representative: &mut [T], a: T::IdentifierType) -> usize
+where
+    T: VertexType,
+    A: Find<T>,
+{
+    let root = A::find(representative, a);
+    (0..representative.len())
+        .filter(|&i| A::find(representative, representative[i].id()) == root)
+        .count()
+}
+
+/// Validation of buffers that did not come out of the algorithm itself, e.g., deserialized ones
+pub trait Validate<T>
+where
+    T: VertexType,
+{
+    /// Checks the invariants the algorithm relies on. The heuristic buffer is left as it was,
+    /// as are the components, but [`Unweighted`] forests may come out with compressed paths
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError`] describing the first node that breaks an invariant
+    fn validate<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+    ) -> Result<(), UnionFindError>;
+}
+
+/// Checks that every parent is in bounds
+fn validate_parents<T: VertexType>(representative: &[T]) -> Result<(), UnionFindError> {
+    let len = representative.len();
+    match (0..len).find(|&i| T::usize(representative[i].id()) >= len) {
+        Some(id) => Err(UnionFindError::CorruptedParent { id }),
+        None => Ok(()),
+    }
+}
+
+/// Checks that every parent is in bounds and that every node reaches a root in `O(N)`
+///
+/// Nodes that are known to reach a root are marked by pointing them straight at it, so no
+/// path is walked twice. This compresses the forest, the components are left as they were
+fn validate_forest<T: VertexType>(representative: &mut [T]) -> Result<(), UnionFindError> {
+    validate_parents(representative)?;
+    for i in 0..representative.len() {
+        FullCompression::try_find(representative, representative[i].id())?;
+    }
+
+    Ok(())
 }
 
 /// Connected operation
@@ -226,6 +686,16 @@ where
     T: VertexType,
 {
     fn connected(representative: &mut [T], a: T::IdentifierType, b: T::IdentifierType) -> bool;
+
+    /// Fallible version of [`Connected::connected`]
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError`] if either node is out of bounds or the buffer is corrupted
+    fn try_connected(
+        representative: &mut [T],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<bool, UnionFindError>;
 }
 
 #[cfg(test)]
@@ -0,0 +1,319 @@
+//! Component member listing wrapper
+
+use core::marker::PhantomData;
+
+use crate::{
+    AlgorithmContainer, Connected, Find, HeuristicType, QuickUnion, SetSize, Union, UnionFind,
+    UnionFindError, UnionResult, Validate, VertexType,
+};
+
+/// [`Linked`] algorithm
+///
+/// Wraps algorithm `A` and links the members of every component in a circular list
+/// that is spliced in `O(1)` on every union, which lets [`UnionFind::members`] run in
+/// time proportional to the size of the component. Every other operation is forwarded to `A`
+///
+/// This algorithm is parameterized by the following
+/// - `A` - Wrapped algorithm. Wrap [`Counted`](crate::Counted) inside to keep both, e.g., `Linked<Counted<QuickUnion>>`
+/// - `IS_SLICE` - boolean value, borrows the sibling buffer instead of owning it
+#[derive(Debug, Default)]
+pub struct Linked<A = QuickUnion, const IS_SLICE: bool = false> {
+    algorithm: PhantomData<A>,
+}
+
+impl<A> AlgorithmContainer for Linked<A, false>
+where
+    A: AlgorithmContainer,
+{
+    type HeuristicContainer<'a, const N: usize> = A::HeuristicContainer<'a, N>;
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> =
+        A::RepresentativeContainer<'a, R, N>;
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; N];
+    type ComponentContainer = A::ComponentContainer;
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = A::HistoryContainer<'a, R, N>;
+}
+
+impl<A> AlgorithmContainer for Linked<A, true>
+where
+    A: AlgorithmContainer,
+{
+    type HeuristicContainer<'a, const N: usize> = A::HeuristicContainer<'a, N>;
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> =
+        A::RepresentativeContainer<'a, R, N>;
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = &'a mut [R::IdentifierType];
+    type ComponentContainer = A::ComponentContainer;
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = A::HistoryContainer<'a, R, N>;
+}
+
+/// Splices every node into the ring of its root. `siblings` must start out with every node pointing to itself
+pub(crate) fn link<A, T>(representative: &mut [T], siblings: &mut [T::IdentifierType])
+where
+    T: VertexType,
+    A: Find<T>,
+{
+    for i in 0..siblings.len() {
+        // only roots are touched before their own turn, so `siblings[i]` is still the id of `i`
+        let root = T::usize(A::find(representative, siblings[i]).id());
+        if root != i {
+            siblings.swap(i, root);
+        }
+    }
+}
+
+impl<'a, A, T, const N: usize> Default for UnionFind<'a, Linked<A>, T, N>
+where
+    T: VertexType,
+    A: AlgorithmContainer,
+    UnionFind<'a, A, T, N>: Default,
+{
+    fn default() -> Self {
+        let uf = UnionFind::<'a, A, T, N>::default();
+        // every node starts out as its own root
+        let siblings = core::array::from_fn(|i| uf.representative.as_ref()[i].id());
+
+        Self {
+            representative: uf.representative,
+            heuristic: uf.heuristic,
+            siblings,
+            components: uf.components,
+            history: uf.history,
+            algorithm: Default::default(),
+        }
+    }
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, Linked<A, true>, T, N>
+where
+    T: VertexType,
+    A: AlgorithmContainer + Find<T>,
+{
+    /// Links the components of `uf` through the borrowed `siblings` buffer
+    ///
+    /// `siblings` must hold the id of every node at its own index, i.e. `siblings[i] == i`
+    ///
+    /// # Panics
+    /// Panics if `siblings` does not have one entry per node or any entry is not the id of its
+    /// own index, rings built on top of it could otherwise loop forever
+    pub fn new(mut uf: UnionFind<'a, A, T, N>, siblings: &'a mut [T::IdentifierType]) -> Self {
+        assert!(
+            siblings.len() == uf.representative.as_ref().len(),
+            "Siblings slice must have one entry per node!"
+        );
+        assert!(
+            siblings
+                .iter()
+                .enumerate()
+                .all(|(i, &id)| T::usize(id) == i),
+            "Siblings slice must hold every node at its own index!"
+        );
+
+        link::<A, T>(uf.representative.as_mut(), siblings);
+
+        Self {
+            representative: uf.representative,
+            heuristic: uf.heuristic,
+            siblings,
+            components: uf.components,
+            history: uf.history,
+            algorithm: Default::default(),
+        }
+    }
+}
+
+impl<'a, A, T, const N: usize, const IS_SLICE: bool> UnionFind<'a, Linked<A, IS_SLICE>, T, N>
+where
+    T: VertexType,
+    Linked<A, IS_SLICE>: AlgorithmContainer,
+{
+    /// Iterates over every member of the component containing `a`, starting with `a` itself
+    pub fn members(&self, a: T::IdentifierType) -> Members<'_, T> {
+        let siblings = self.siblings.as_ref();
+        assert!(T::usize(a) < siblings.len());
+
+        Members {
+            siblings,
+            start: a,
+            current: Some(a),
+        }
+    }
+}
+
+/// Iterator over the members of a component, see [`UnionFind::members`]
+#[derive(Debug)]
+pub struct Members<'b, T>
+where
+    T: VertexType,
+{
+    siblings: &'b [T::IdentifierType],
+    start: T::IdentifierType,
+    current: Option<T::IdentifierType>,
+}
+
+impl<T> Iterator for Members<'_, T>
+where
+    T: VertexType,
+{
+    type Item = T::IdentifierType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current?;
+        let next = self.siblings[T::usize(current)];
+        self.current = if next == self.start { None } else { Some(next) };
+        Some(current)
+    }
+}
+
+impl<A, T, const IS_SLICE: bool> Connected<T> for Linked<A, IS_SLICE>
+where
+    T: VertexType,
+    A: Connected<T>,
+{
+    fn connected(representative: &mut [T], a: T::IdentifierType, b: T::IdentifierType) -> bool {
+        A::connected(representative, a, b)
+    }
+
+    fn try_connected(
+        representative: &mut [T],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<bool, UnionFindError> {
+        A::try_connected(representative, a, b)
+    }
+}
+
+impl<A, T, const IS_SLICE: bool> Union<T> for Linked<A, IS_SLICE>
+where
+    T: VertexType,
+    A: Union<T>,
+{
+    fn union_sets<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> UnionResult<T::IdentifierType> {
+        A::union_sets(representative, heuristic, a, b)
+    }
+
+    fn try_union_sets<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<UnionResult<T::IdentifierType>, UnionFindError> {
+        A::try_union_sets(representative, heuristic, a, b)
+    }
+}
+
+impl<A, T, const IS_SLICE: bool> Find<T> for Linked<A, IS_SLICE>
+where
+    T: VertexType,
+    A: Find<T>,
+{
+    fn find(representative: &mut [T], a: T::IdentifierType) -> T {
+        A::find(representative, a)
+    }
+
+    fn try_find(representative: &mut [T], a: T::IdentifierType) -> Result<T, UnionFindError> {
+        A::try_find(representative, a)
+    }
+}
+
+impl<A, T, const IS_SLICE: bool> SetSize<T> for Linked<A, IS_SLICE>
+where
+    T: VertexType,
+    A: SetSize<T>,
+{
+    fn set_size<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &[S],
+        a: T::IdentifierType,
+    ) -> usize {
+        A::set_size(representative, heuristic, a)
+    }
+}
+
+impl<A, T, const IS_SLICE: bool> Validate<T> for Linked<A, IS_SLICE>
+where
+    T: VertexType,
+    A: Validate<T>,
+{
+    fn validate<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+    ) -> Result<(), UnionFindError> {
+        A::validate(representative, heuristic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ByRank, Counted, Linked, QuickFind, QuickUnion, UnionFind};
+    use core::mem;
+
+    fn sorted<const N: usize>(members: impl Iterator<Item = u8>) -> heapless::Vec<u8, N> {
+        let mut members = members.collect::<heapless::Vec<_, N>>();
+        members.sort_unstable();
+        members
+    }
+
+    #[test]
+    fn test_members() {
+        let mut uf = UnionFind::<Linked<QuickUnion>, u8, 10>::default();
+        assert_eq!([4], sorted::<10>(uf.members(4)));
+        uf.union_sets(4, 3);
+        uf.union_sets(3, 8);
+        uf.union_sets(6, 5);
+        uf.union_sets(9, 4);
+        uf.union_sets(9, 3);
+        assert_eq!([3, 4, 8, 9], sorted::<10>(uf.members(8)));
+        assert_eq!([5, 6], sorted::<10>(uf.members(6)));
+        assert_eq!(Some(4), uf.members(4).next());
+
+        let mut uf = UnionFind::<Linked<Counted<QuickFind>>, u8, 10>::default();
+        uf.union_sets(1, 2);
+        uf.union_sets(2, 0);
+        assert_eq!([0, 1, 2], sorted::<10>(uf.members(0)));
+        assert_eq!(8, uf.count());
+    }
+
+    #[test]
+    fn test_members_slice() {
+        let mut representative = [0u8, 0, 2, 3, 2];
+        let mut heuristic = [1, 0, 1, 0, 0];
+        let mut siblings = [0u8, 1, 2, 3, 4];
+        let uf =
+            UnionFind::<QuickUnion<ByRank<true>>, u8, 5>::new(&mut representative, &mut heuristic);
+        let mut uf =
+            UnionFind::<Linked<QuickUnion<ByRank<true>>, true>, u8, 5>::new(uf, &mut siblings);
+        assert_eq!([0, 1], sorted::<5>(uf.members(1)));
+        assert_eq!([2, 4], sorted::<5>(uf.members(2)));
+        uf.union_sets(3, 4);
+        assert_eq!([2, 3, 4], sorted::<5>(uf.members(3)));
+        assert_eq!([0, 1], sorted::<5>(uf.members(0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_members_slice_invalid() {
+        let mut representative = [0u8, 1, 2];
+        let mut heuristic = [0; 3];
+        // 2 -> 1 -> 0 -> 1 never returns to 2
+        let mut siblings = [1u8, 0, 1];
+        let uf =
+            UnionFind::<QuickUnion<ByRank<true>>, u8, 3>::new(&mut representative, &mut heuristic);
+        UnionFind::<Linked<QuickUnion<ByRank<true>>, true>, u8, 3>::new(uf, &mut siblings);
+    }
+
+    #[test]
+    fn test_linked_sz() {
+        assert_eq!(
+            mem::size_of::<UnionFind::<'_, QuickUnion, u32, 10>>() + mem::size_of::<[u32; 10]>(),
+            mem::size_of::<UnionFind::<'_, Linked<QuickUnion>, u32, 10>>()
+        );
+        assert_eq!(
+            mem::size_of::<&'_ [u32]>() * 2,
+            mem::size_of::<UnionFind::<'_, Linked<QuickFind<true>, true>, u32, 10>>()
+        );
+    }
+}
@@ -0,0 +1,338 @@
+//! Zero-copy union find over raw bytes
+
+use core::marker::PhantomData;
+
+use crate::grow::{generate_forwarding_impl, Grow};
+use crate::potential::Weighted;
+use crate::rollback::Reversible;
+use crate::{
+    AlgorithmContainer, Find, HeuristicType, QuickUnion, UnionFind, UnionFindError, Validate,
+    VertexType,
+};
+
+/// Little-endian unsigned integer stored as plain bytes, i.e. `[u8; 2]`, `[u8; 4]` or `[u8; 8]`
+///
+/// Words are [`VertexType`]s and [`HeuristicType`]s, so every algorithm runs on mapped parents
+/// and heuristics just like on native integers
+pub trait Word: VertexType + HeuristicType {
+    /// Largest value a word can hold on this target
+    const MAX: usize;
+
+    /// Splits `bytes` into words, returning the words and the bytes left over
+    fn from_bytes(bytes: &mut [u8]) -> (&mut [Self], &mut [u8]);
+}
+
+macro_rules! generate_word_impl {
+    ($($width:literal => $num_type:ident), *) => {
+        $(
+            impl VertexType for [u8; $width] {
+                type IdentifierType = $num_type;
+
+                #[inline(always)]
+                fn id(&self) -> $num_type {
+                    $num_type::from_le_bytes(*self)
+                }
+
+                #[inline(always)]
+                fn usize(a: $num_type) -> usize {
+                    a as usize
+                }
+            }
+
+            impl HeuristicType for [u8; $width] {
+                #[inline(always)]
+                fn get(&self) -> usize {
+                    $num_type::from_le_bytes(*self) as usize
+                }
+
+                #[inline(always)]
+                fn set(&mut self, value: usize) {
+                    *self = (value as $num_type).to_le_bytes();
+                }
+            }
+
+            impl Word for [u8; $width] {
+                const MAX: usize = if core::mem::size_of::<$num_type>() < core::mem::size_of::<usize>() {
+                    $num_type::MAX as usize
+                } else {
+                    usize::MAX
+                };
+
+                #[inline(always)]
+                fn from_bytes(bytes: &mut [u8]) -> (&mut [Self], &mut [u8]) {
+                    bytes.as_chunks_mut::<$width>()
+                }
+            }
+        )*
+    };
+}
+
+generate_word_impl!(2 => u16, 4 => u32, 8 => u64);
+
+/// [`Mapped`] algorithm
+///
+/// Wraps algorithm `A` and operates in place on a borrowed byte buffer, e.g. a memory-mapped
+/// file or a dedicated RAM region. Every operation is forwarded to `A`
+///
+/// The buffer holds the parent array followed by the heuristic array, both made of
+/// little-endian [`Word`]s, so every value sits at an offset aligned to its width and the same
+/// bytes are valid on every target. The number of nodes is derived from the length of the
+/// buffer, see [`UnionFind::bytes_for`]. Nothing is copied, the words are decoded on every access
+///
+/// This algorithm is parameterized by the following
+/// - `W` - Word type. Available types: `[u8; 2]`, `[u8; 4]`, `[u8; 8]`
+/// - `A` - Algorithm type, any algorithm that implements [`Grow`]
+///
+/// # Example
+/// ```
+/// use pulau_rs::{MappedUnionFind, QuickUnion, BySize};
+///
+/// let mut bytes = [0u8; MappedUnionFind::<[u8; 4], QuickUnion<BySize>>::bytes_for(3)];
+/// let mut uf = MappedUnionFind::<[u8; 4], QuickUnion<BySize>>::init(&mut bytes).unwrap();
+/// uf.union_sets(0, 2);
+/// assert_eq!(2, uf.set_size(0));
+///
+/// // the bytes are the state, reopening picks it up again
+/// let mut uf = MappedUnionFind::<[u8; 4], QuickUnion<BySize>>::new(&mut bytes).unwrap();
+/// assert!(uf.connected(2, 0));
+/// assert!(uf.try_find(3).is_err());
+/// ```
+#[derive(Debug, Default)]
+pub struct Mapped<W, A = QuickUnion> {
+    algorithm: PhantomData<(W, A)>,
+}
+
+/// Union find over a byte buffer, see [`Mapped`]
+pub type MappedUnionFind<'a, W, A = QuickUnion> = UnionFind<'a, Mapped<W, A>, W, 0>;
+
+impl<W, A> AlgorithmContainer for Mapped<W, A>
+where
+    W: Word + 'static,
+    A: Grow,
+{
+    type HeuristicContainer<'a, const N: usize> = &'a mut [W];
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = &'a mut [R];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = A::HistoryContainer<'a, R, N>;
+}
+
+impl<'a, W, A, const N: usize> UnionFind<'a, Mapped<W, A>, W, N>
+where
+    W: Word + 'static,
+    A: Grow,
+    A::HistoryContainer<'a, W, N>: Default,
+{
+    /// Number of bytes taken by `n` nodes
+    pub const fn bytes_for(n: usize) -> usize {
+        let arrays = if A::SINGLETON.is_some() { 2 } else { 1 };
+        n * core::mem::size_of::<W>() * arrays
+    }
+
+    /// Operates on a buffer that already holds a union find, after checking that it is
+    /// well-formed
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError::InvalidLayout`] if the length of `bytes` does not match
+    /// [`UnionFind::bytes_for`] for any number of nodes that fits into `W`, or
+    /// [`UnionFindError`] describing the first node that breaks an invariant of `A`
+    pub fn new(bytes: &'a mut [u8]) -> Result<Self, UnionFindError>
+    where
+        A: Validate<W> + Find<W>,
+    {
+        let mut uf = Self::split(bytes)?;
+        uf.validate()?;
+        Ok(uf)
+    }
+
+    /// Operates on a buffer after resetting it to singletons
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError::InvalidLayout`] if the length of `bytes` does not match
+    /// [`UnionFind::bytes_for`] for any number of nodes that fits into `W`
+    pub fn init(bytes: &'a mut [u8]) -> Result<Self, UnionFindError> {
+        let uf = Self::split(bytes)?;
+        for (i, parent) in uf.representative.iter_mut().enumerate() {
+            parent.set(i);
+        }
+        if let Some(singleton) = A::SINGLETON {
+            for heuristic in uf.heuristic.iter_mut() {
+                heuristic.set(singleton);
+            }
+        }
+        Ok(uf)
+    }
+
+    fn split(bytes: &'a mut [u8]) -> Result<Self, UnionFindError> {
+        let len = bytes.len();
+        let (words, rest) = W::from_bytes(bytes);
+        let arrays = if A::SINGLETON.is_some() { 2 } else { 1 };
+        let n = words.len() / arrays;
+        // every id and every size up to `n` must fit into a word
+        if !rest.is_empty() || words.len() % arrays != 0 || n > W::MAX {
+            return Err(UnionFindError::InvalidLayout { len });
+        }
+
+        let (representative, heuristic) = words.split_at_mut(n);
+        Ok(Self {
+            representative,
+            heuristic,
+            siblings: [],
+            components: [0; 0],
+            history: Default::default(),
+            algorithm: PhantomData,
+        })
+    }
+}
+
+impl<W, A> Weighted for Mapped<W, A>
+where
+    W: Word + 'static,
+    A: Weighted + Grow,
+{
+    type Heuristic = A::Heuristic;
+}
+
+impl<W, A> Reversible for Mapped<W, A>
+where
+    W: Word + 'static,
+    A: Reversible + Grow,
+{
+}
+
+generate_forwarding_impl!(W, A => Mapped<W, A>);
+
+#[cfg(test)]
+mod tests {
+    use super::MappedUnionFind;
+    use crate::{
+        ByRank, BySize, Counted, FullCompression, Linked, QuickFind, QuickUnion, QuickUnionWith,
+        Rem, UnionFind, UnionFindError, UnionResult, Unweighted,
+    };
+
+    #[test]
+    fn test_mapped() {
+        let mut bytes = [0xAAu8; 48];
+        let mut uf = MappedUnionFind::<'_, [u8; 4]>::init(&mut bytes).unwrap();
+        assert_eq!(6, uf.representative().len());
+        assert_eq!(
+            UnionResult::Merged {
+                root: 0,
+                absorbed: 1
+            },
+            uf.union_sets(0, 1)
+        );
+        uf.union_sets(1, 2);
+        uf.union_sets(4, 5);
+        assert!(uf.connected(2, 0));
+        assert!(!uf.connected(3, 4));
+        assert_eq!(3, uf.set_size(2));
+        assert_eq!(
+            Err(UnionFindError::OutOfBounds { id: 6, len: 6 }),
+            uf.try_find(6)
+        );
+        assert!(uf.try_union_sets(0, 9).is_err());
+
+        // the bytes are the state, reopening picks it up again
+        assert_eq!([0, 0, 0, 0, 0, 0, 0, 0], bytes[..8]);
+        assert_eq!([1, 0, 0, 0], bytes[24..28]);
+        let mut uf = MappedUnionFind::<'_, [u8; 4]>::new(&mut bytes).unwrap();
+        assert!(uf.connected(1, 2));
+        assert_eq!(
+            UnionResult::AlreadyConnected { root: 4 },
+            uf.union_sets(5, 4)
+        );
+    }
+
+    #[test]
+    fn test_mapped_size() {
+        let mut bytes = [0u8; 2 * 2 * 300];
+        let mut uf = MappedUnionFind::<'_, [u8; 2], QuickUnionWith<BySize, FullCompression>>::init(
+            &mut bytes,
+        )
+        .unwrap();
+        for i in 1..300 {
+            uf.union_sets(i - 1, i);
+        }
+        assert_eq!(300, uf.set_size(123));
+        assert!(MappedUnionFind::<'_, [u8; 2], QuickUnion<BySize>>::new(&mut bytes).is_ok());
+
+        let mut bytes = [0u8; 8 * 4];
+        let mut uf =
+            MappedUnionFind::<'_, [u8; 8], QuickUnion<Unweighted>>::init(&mut bytes).unwrap();
+        uf.union_sets(3, 0);
+        assert_eq!(Some(&0u64.to_le_bytes()), uf.representative().get(3));
+        assert!(uf.heuristic().is_empty());
+        assert_eq!(2, uf.set_size(0));
+
+        let mut bytes = [0u8; 4 * 3];
+        let mut uf = MappedUnionFind::<'_, [u8; 4], QuickFind>::init(&mut bytes).unwrap();
+        uf.union_sets(0, 2);
+        assert!(uf.connected(2, 0));
+        assert!(uf.validate().is_ok());
+    }
+
+    #[test]
+    fn test_mapped_wrappers() {
+        let mut bytes = [0u8; 2 * 2 * 5];
+        let uf = MappedUnionFind::<'_, [u8; 2], QuickUnion<BySize>>::init(&mut bytes).unwrap();
+        let mut siblings = [0, 1, 2, 3, 4];
+        let uf: UnionFind<'_, Counted<_>, _, 0> = uf.into();
+        let mut uf = UnionFind::<Linked<_, true>, _, 0>::new(uf, &mut siblings);
+        uf.union_sets(0, 3);
+        uf.union_sets(4, 3);
+        assert_eq!(3, uf.count());
+        assert!(uf.validate().is_ok());
+
+        let mut members = uf.members(4).collect::<heapless::Vec<_, 5>>();
+        members.sort_unstable();
+        assert_eq!([0, 3, 4], members);
+
+        let mut dot = heapless::String::<512>::new();
+        uf.write_dot(&mut dot).unwrap();
+        assert!(dot.contains("size"));
+    }
+
+    #[test]
+    fn test_mapped_invalid() {
+        assert_eq!(
+            Some(UnionFindError::InvalidLayout { len: 7 }),
+            MappedUnionFind::<'_, [u8; 2]>::init(&mut [0; 7]).err()
+        );
+        assert_eq!(
+            Some(UnionFindError::InvalidLayout { len: 6 }),
+            MappedUnionFind::<'_, [u8; 2]>::init(&mut [0; 6]).err()
+        );
+
+        // parent out of range, cycle, rank not increasing towards the root
+        let mut bytes = [0, 0, 7, 0, 0, 0, 0, 0];
+        assert!(MappedUnionFind::<'_, [u8; 2]>::new(&mut bytes).is_err());
+        let mut bytes = [1, 0, 0, 0, 0, 0, 0, 0];
+        assert!(MappedUnionFind::<'_, [u8; 2]>::new(&mut bytes).is_err());
+        let mut bytes = [1, 0, 1, 0, 0, 0, 0, 0];
+        assert_eq!(
+            Some(UnionFindError::InconsistentHeuristic { id: 0 }),
+            MappedUnionFind::<'_, [u8; 2]>::new(&mut bytes).err()
+        );
+        let mut bytes = [1, 0, 1, 0, 0, 0, 1, 0];
+        assert!(MappedUnionFind::<'_, [u8; 2]>::new(&mut bytes).is_ok());
+        let mut bytes = [1, 0, 1, 0, 0, 0, 2, 0];
+        assert!(MappedUnionFind::<'_, [u8; 2], QuickUnion<ByRank>>::new(&mut bytes).is_err());
+        let mut bytes = [1, 0, 1, 0, 1, 0, 1, 0];
+        assert!(MappedUnionFind::<'_, [u8; 2], QuickUnion<BySize>>::new(&mut bytes).is_err());
+    }
+
+    #[test]
+    fn test_mapped_words_as_vertices() {
+        let mut bytes = [0u8; 4 * 4];
+        let (parents, _) = bytes.as_chunks_mut::<4>();
+        for (i, parent) in parents.iter_mut().enumerate() {
+            *parent = (i as u32).to_le_bytes();
+        }
+
+        let mut uf = UnionFind::<Rem<true>, [u8; 4], 4>::new(parents);
+        uf.union_sets(3, 1);
+        assert!(uf.connected(1, 3));
+        assert_eq!([1, 0, 0, 0], bytes[12..]);
+    }
+}
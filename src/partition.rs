@@ -0,0 +1,153 @@
+//! Partition export
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{AlgorithmContainer, Find, UnionFind, VertexType};
+
+/// Converts an index into an identifier, the index must come from a valid representative buffer
+pub(crate) fn identifier<T>(i: usize) -> T::IdentifierType
+where
+    T: VertexType,
+    T::IdentifierType: TryFrom<usize>,
+{
+    match T::IdentifierType::try_from(i) {
+        Ok(id) => id,
+        Err(_) => panic!("Index {} does not fit into the identifier type!", i),
+    }
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, A, T, N>
+where
+    T: VertexType,
+    A: AlgorithmContainer,
+{
+    /// Iterates over the root of every component in ascending order
+    pub fn roots(&self) -> impl Iterator<Item = T::IdentifierType> + '_ {
+        self.representative
+            .as_ref()
+            .iter()
+            .enumerate()
+            .filter(|(i, node)| T::usize(node.id()) == *i)
+            .map(|(_, node)| node.id())
+    }
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, A, T, N>
+where
+    T: VertexType,
+    T::IdentifierType: TryFrom<usize>,
+    A: AlgorithmContainer + Find<T>,
+{
+    /// Writes a dense component label in `0..k` for every node into `out` and returns `k`,
+    /// the number of components
+    ///
+    /// Labels are canonical: components are numbered in the order of their smallest node,
+    /// regardless of which node ended up as the root. `out` doubles as scratch space, so this
+    /// runs in `O(N)` finds without allocating
+    ///
+    /// # Panics
+    /// Panics if `out` is shorter than the representative buffer
+    pub fn labels(&mut self, out: &mut [T::IdentifierType]) -> usize {
+        let representative = self.representative.as_mut();
+        let len = representative.len();
+        assert!(out.len() >= len, "Label slice must have at least len >= N!");
+
+        let is_root = |representative: &[T], i: usize| T::usize(representative[i].id()) == i;
+
+        // non-roots remember their root while roots track their smallest node,
+        // walking downwards leaves the minimum as the last write
+        for i in (0..len).rev() {
+            let root = T::usize(A::find(representative, representative[i].id()).id());
+            if root != i {
+                out[i] = identifier::<T>(root);
+            }
+            out[root] = identifier::<T>(i);
+        }
+
+        // the smallest node of a component is the first to be visited and moves the label into
+        // the slot of its root. Any label already stored there is at most the index of that
+        // smallest node, so it can never be mistaken for the index of a later node
+        let mut count = 0;
+        for i in 0..len {
+            let root = if is_root(representative, i) {
+                i
+            } else {
+                T::usize(out[i])
+            };
+
+            if root >= i && T::usize(out[root]) == i {
+                out[root] = identifier::<T>(count);
+                count += 1;
+            }
+            out[i] = out[root];
+        }
+
+        count
+    }
+
+    /// Collects the nodes of every component, components are ordered as in [`UnionFind::labels`]
+    /// and nodes are ascending within each component
+    #[cfg(feature = "alloc")]
+    pub fn groups(&mut self) -> Vec<Vec<T::IdentifierType>> {
+        let len = self.representative.as_ref().len();
+        let mut labels = (0..len).map(identifier::<T>).collect::<Vec<_>>();
+        let count = self.labels(&mut labels);
+
+        let mut groups = (0..count).map(|_| Vec::new()).collect::<Vec<_>>();
+        for (i, label) in labels.into_iter().enumerate() {
+            groups[T::usize(label)].push(identifier::<T>(i));
+        }
+
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{QuickFind, QuickUnion, UnionFind, Unweighted};
+
+    #[test]
+    fn test_roots() {
+        let mut uf = UnionFind::<QuickUnion, u8, 6>::default();
+        uf.union_sets(4, 3);
+        uf.union_sets(1, 5);
+        uf.union_sets(0, 5);
+        let mut roots = uf.roots();
+        assert_eq!(Some(1), roots.next());
+        assert_eq!(Some(2), roots.next());
+        assert_eq!(Some(4), roots.next());
+        assert_eq!(None, roots.next());
+    }
+
+    #[test]
+    fn test_labels() {
+        let mut uf = UnionFind::<QuickUnion<Unweighted, false>, u8, 8>::default();
+        uf.union_sets(0, 6);
+        uf.union_sets(3, 7);
+        uf.union_sets(1, 7);
+        uf.union_sets(5, 2);
+        let mut labels = [0; 8];
+        assert_eq!(4, uf.labels(&mut labels));
+        assert_eq!([0, 1, 2, 1, 3, 2, 0, 1], labels);
+
+        let mut uf = UnionFind::<QuickFind, u32, 6>::default();
+        uf.union_sets(5, 4);
+        uf.union_sets(2, 1);
+        let mut labels = [9; 6];
+        assert_eq!(4, uf.labels(&mut labels));
+        assert_eq!([0, 1, 1, 2, 3, 3], labels);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_groups() {
+        use alloc::vec;
+
+        let mut uf = UnionFind::<QuickUnion, u16, 6>::default();
+        uf.union_sets(5, 0);
+        uf.union_sets(3, 4);
+        uf.union_sets(4, 0);
+        assert_eq!(vec![vec![0, 3, 4, 5], vec![1], vec![2]], uf.groups());
+    }
+}
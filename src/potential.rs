@@ -0,0 +1,625 @@
+//! Potential (weighted) union find implementation
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::AddAssign;
+
+use crate::quickunion::{Compression, Heuristic, NoCompression};
+use crate::snapshot::Encode;
+use crate::{
+    AlgorithmContainer, ByRank, BySize, Connected, Counted, Find, HeuristicType, Linked,
+    QuickUnionWith, SetSize, Union, UnionFind, UnionFindError, UnionResult, Validate, VertexType,
+};
+
+/// Abelian group that potentials are combined in
+///
+/// Implemented for the signed integers, which wrap on overflow, and for `bool` as XOR parity
+pub trait Group: Copy + Eq {
+    fn zero() -> Self;
+    fn add(self, other: Self) -> Self;
+    fn neg(self) -> Self;
+}
+
+macro_rules! generate_group_impl {
+    ($($num_type:ident), *) => {
+        $(
+            impl Group for $num_type {
+                #[inline(always)]
+                fn zero() -> Self {
+                    0
+                }
+
+                #[inline(always)]
+                fn add(self, other: Self) -> Self {
+                    self.wrapping_add(other)
+                }
+
+                #[inline(always)]
+                fn neg(self) -> Self {
+                    self.wrapping_neg()
+                }
+            }
+        )*
+    };
+}
+
+generate_group_impl!(i8, i16, i32, i64, i128, isize);
+
+impl Group for bool {
+    #[inline(always)]
+    fn zero() -> Self {
+        false
+    }
+
+    #[inline(always)]
+    fn add(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+/// Numeric [`VertexType`] that identifies itself, i.e. `u8`, `u16`, `u32`, `u64` and `usize`
+pub trait Identifier: VertexType<IdentifierType = Self> + PartialOrd + AddAssign {}
+
+impl<I> Identifier for I where I: VertexType<IdentifierType = I> + PartialOrd + AddAssign {}
+
+/// Node of a [`Potential`] union find, its potential is its value minus the value of its parent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PotentialVertex<I, W> {
+    parent: I,
+    potential: W,
+}
+
+impl<I, W> PotentialVertex<I, W>
+where
+    W: Group,
+{
+    /// Creates a root, i.e. `id` must be the index of the node
+    pub fn new(id: I) -> Self {
+        Self {
+            parent: id,
+            potential: W::zero(),
+        }
+    }
+}
+
+impl<I, W> VertexType for PotentialVertex<I, W>
+where
+    I: Identifier,
+    W: Group,
+{
+    type IdentifierType = I;
+
+    #[inline(always)]
+    fn id(&self) -> I {
+        self.parent
+    }
+
+    #[inline(always)]
+    fn usize(a: I) -> usize {
+        I::usize(a)
+    }
+}
+
+impl<I, W> Encode for PotentialVertex<I, W>
+where
+    I: Encode,
+    W: Encode,
+{
+    const WIDTH: u8 = I::WIDTH + W::WIDTH;
+
+    fn encode(&self, buf: &mut [u8]) {
+        let (parent, potential) = buf.split_at_mut(usize::from(I::WIDTH));
+        self.parent.encode(parent);
+        self.potential.encode(potential);
+    }
+
+    fn decode(buf: &[u8]) -> Option<Self> {
+        let (parent, potential) = buf.split_at(usize::from(I::WIDTH));
+        Some(Self {
+            parent: I::decode(parent)?,
+            potential: W::decode(potential)?,
+        })
+    }
+}
+
+/// Returned by [`UnionFind::union_with`] when the constraint conflicts with earlier ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction<W> {
+    /// The difference implied by the earlier constraints
+    pub diff: W,
+}
+
+impl<W: fmt::Debug> fmt::Display for Contradiction<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "constraint contradicts the existing difference {:?}",
+            self.diff
+        )
+    }
+}
+
+/// [`Potential`] algorithm
+///
+/// Union by rank or size where every node also stores a potential relative to its parent,
+/// see [`PotentialVertex`]. Finds sum the potentials on the way to the root and compress
+/// the path, so [`UnionFind::union_with`] and [`UnionFind::diff`] run in `Θ(α(N))`
+///
+/// This algorithm is parameterized by the following
+/// - `H` - Heuristic Type. Available types: [`ByRank`], [`BySize`]
+#[derive(Debug, Default)]
+pub struct Potential<H = ByRank> {
+    heuristic: PhantomData<H>,
+}
+
+/// Algorithms whose nodes carry potentials
+pub trait Weighted: AlgorithmContainer {
+    /// Heuristic used to link 2 roots
+    type Heuristic: Heuristic;
+}
+
+impl<H> Weighted for Potential<H>
+where
+    H: Heuristic,
+    Self: AlgorithmContainer,
+{
+    type Heuristic = H;
+}
+
+impl<A> Weighted for Counted<A>
+where
+    A: Weighted,
+{
+    type Heuristic = A::Heuristic;
+}
+
+impl<A, const IS_SLICE: bool> Weighted for Linked<A, IS_SLICE>
+where
+    A: Weighted,
+    Self: AlgorithmContainer,
+{
+    type Heuristic = A::Heuristic;
+}
+
+impl AlgorithmContainer for Potential<ByRank> {
+    type HeuristicContainer<'a, const N: usize> = [usize; N];
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = [R; N];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
+}
+
+impl AlgorithmContainer for Potential<BySize> {
+    type HeuristicContainer<'a, const N: usize> = [usize; N];
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = [R; N];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
+}
+
+impl AlgorithmContainer for Potential<ByRank<true>> {
+    type HeuristicContainer<'a, const N: usize> = &'a mut [usize];
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = &'a mut [R];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
+}
+
+impl AlgorithmContainer for Potential<BySize<true>> {
+    type HeuristicContainer<'a, const N: usize> = &'a mut [usize];
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = &'a mut [R];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
+}
+
+macro_rules! generate_default_ctor_potential {
+    ($($num_type:ident), *) => {
+        $(
+        impl<W: Group, const N: usize> Default for UnionFind<'_, Potential<ByRank>, PotentialVertex<$num_type, W>, N>
+        {
+            fn default() -> Self {
+                let mut representative = [PotentialVertex::new(0); N];
+
+                for i in 0..(N as $num_type) {
+                    representative[i as usize] = PotentialVertex::new(i);
+                }
+
+                Self {
+                    representative,
+                    heuristic: [0; N],
+                    siblings: [],
+                    components: [0; 0],
+                    history: (),
+                    algorithm: Default::default(),
+                }
+            }
+        }
+
+        impl<W: Group, const N: usize> Default for UnionFind<'_, Potential<BySize>, PotentialVertex<$num_type, W>, N>
+        {
+            fn default() -> Self {
+                let mut representative = [PotentialVertex::new(0); N];
+
+                for i in 0..(N as $num_type) {
+                    representative[i as usize] = PotentialVertex::new(i);
+                }
+
+                Self {
+                    representative,
+                    heuristic: [1; N],
+                    siblings: [],
+                    components: [0; 0],
+                    history: (),
+                    algorithm: Default::default(),
+                }
+            }
+        }
+        )*
+    };
+}
+
+impl<'a, H, T, const N: usize> UnionFind<'a, Potential<H>, T, N>
+where
+    T: VertexType + 'a,
+    Potential<H>: AlgorithmContainer<
+        HeuristicContainer<'a, N> = &'a mut [usize],
+        RepresentativeContainer<'a, T, N> = &'a mut [T],
+        SiblingContainer<'a, T, N> = [T::IdentifierType; 0],
+        ComponentContainer = [usize; 0],
+        HistoryContainer<'a, T, N> = (),
+    >,
+{
+    pub fn new(representative: &'a mut [T], heuristic: &'a mut [usize]) -> Self {
+        debug_assert!(
+            representative.len() >= N,
+            "Representative slice must have at least len >= N!"
+        );
+        debug_assert!(
+            heuristic.len() >= N,
+            "Heuristic slice must have at least len >= N!"
+        );
+
+        Self {
+            representative,
+            heuristic,
+            siblings: [],
+            components: [0; 0],
+            history: (),
+            algorithm: Default::default(),
+        }
+    }
+}
+
+/// Finds the root of `a` and the potential of `a` relative to it, pointing every node on the
+/// path straight at the root
+fn find_potential<I, W>(representative: &mut [PotentialVertex<I, W>], a: I) -> (I, W)
+where
+    I: Identifier,
+    W: Group,
+{
+    let (mut root, mut total) = (a, W::zero());
+    while root != representative[I::usize(root)].parent {
+        total = total.add(representative[I::usize(root)].potential);
+        root = representative[I::usize(root)].parent;
+    }
+
+    // every node peels its own potential off the total before handing it to its parent
+    let (mut node, mut rest) = (a, total);
+    while node != root {
+        let PotentialVertex { parent, potential } = representative[I::usize(node)];
+        representative[I::usize(node)] = PotentialVertex {
+            parent: root,
+            potential: rest,
+        };
+        rest = rest.add(potential.neg());
+        node = parent;
+    }
+
+    (root, total)
+}
+
+/// Links the distinct roots of `a` and `b` so that the value of `a` minus the value of `b` is `delta`
+fn link<H, I, W, S>(
+    representative: &mut [PotentialVertex<I, W>],
+    heuristic: &mut [S],
+    (root_a, potential_a): (I, W),
+    (root_b, potential_b): (I, W),
+    delta: W,
+) -> UnionResult<I>
+where
+    H: Heuristic,
+    I: Identifier,
+    W: Group,
+    S: HeuristicType,
+{
+    let result = H::handle_decision(root_a, root_b, heuristic, representative);
+    if let UnionResult::Merged { absorbed, .. } = result {
+        // value of `root_b` minus the value of `root_a`
+        let offset = potential_a.add(delta.neg()).add(potential_b.neg());
+        representative[I::usize(absorbed)].potential = if absorbed == root_b {
+            offset
+        } else {
+            offset.neg()
+        };
+    }
+
+    result
+}
+
+impl<'a, A, I, W, const N: usize> UnionFind<'a, A, PotentialVertex<I, W>, N>
+where
+    I: Identifier + 'a,
+    W: Group + 'a,
+    A: Weighted,
+{
+    /// Unions the components of `a` and `b` under the constraint that the value of `a` minus
+    /// the value of `b` is `delta`
+    ///
+    /// # Errors
+    /// Returns [`Contradiction`] if `a` and `b` are already connected with a different difference,
+    /// nothing is modified in that case
+    pub fn union_with(&mut self, a: I, b: I, delta: W) -> Result<UnionResult<I>, Contradiction<W>> {
+        let representative = self.representative.as_mut();
+        let (root_a, potential_a) = find_potential(representative, a);
+        let (root_b, potential_b) = find_potential(representative, b);
+
+        if root_a == root_b {
+            let diff = potential_a.add(potential_b.neg());
+            return if diff == delta {
+                Ok(UnionResult::AlreadyConnected { root: root_a })
+            } else {
+                Err(Contradiction { diff })
+            };
+        }
+
+        let result = link::<A::Heuristic, I, W, _>(
+            representative,
+            self.heuristic.as_mut(),
+            (root_a, potential_a),
+            (root_b, potential_b),
+            delta,
+        );
+        self.record(&result);
+        Ok(result)
+    }
+
+    /// Value of `a` minus the value of `b`, or `None` if they are not connected
+    pub fn diff(&mut self, a: I, b: I) -> Option<W> {
+        let representative = self.representative.as_mut();
+        let (root_a, potential_a) = find_potential(representative, a);
+        let (root_b, potential_b) = find_potential(representative, b);
+        (root_a == root_b).then(|| potential_a.add(potential_b.neg()))
+    }
+}
+
+impl<H, I, W> Connected<PotentialVertex<I, W>> for Potential<H>
+where
+    I: Identifier,
+    W: Group,
+{
+    fn connected(representative: &mut [PotentialVertex<I, W>], a: I, b: I) -> bool {
+        Self::find(representative, a) == Self::find(representative, b)
+    }
+
+    fn try_connected(
+        representative: &mut [PotentialVertex<I, W>],
+        a: I,
+        b: I,
+    ) -> Result<bool, UnionFindError> {
+        Ok(Self::try_find(representative, a)? == Self::try_find(representative, b)?)
+    }
+}
+
+impl<H, I, W> Union<PotentialVertex<I, W>> for Potential<H>
+where
+    H: Heuristic,
+    I: Identifier,
+    W: Group,
+{
+    /// Unions without a constraint check, i.e. like [`UnionFind::union_with`] with a zero
+    /// `delta` that is silently dropped if `a` and `b` are already connected
+    fn union_sets<S: HeuristicType>(
+        representative: &mut [PotentialVertex<I, W>],
+        heuristic: &mut [S],
+        a: I,
+        b: I,
+    ) -> UnionResult<I> {
+        let a = find_potential(representative, a);
+        let b = find_potential(representative, b);
+        if a.0 == b.0 {
+            return UnionResult::AlreadyConnected { root: a.0 };
+        }
+
+        link::<H, I, W, S>(representative, heuristic, a, b, W::zero())
+    }
+
+    fn try_union_sets<S: HeuristicType>(
+        representative: &mut [PotentialVertex<I, W>],
+        heuristic: &mut [S],
+        a: I,
+        b: I,
+    ) -> Result<UnionResult<I>, UnionFindError> {
+        let roots = [
+            Self::try_find(representative, a)?.id(),
+            Self::try_find(representative, b)?.id(),
+        ];
+        for root in roots {
+            if I::usize(root) >= heuristic.len() {
+                return Err(UnionFindError::HeuristicOutOfBounds {
+                    id: I::usize(root),
+                    len: heuristic.len(),
+                });
+            }
+        }
+
+        Ok(Self::union_sets(representative, heuristic, a, b))
+    }
+}
+
+impl<H, I, W> Find<PotentialVertex<I, W>> for Potential<H>
+where
+    I: Identifier,
+    W: Group,
+{
+    fn find(representative: &mut [PotentialVertex<I, W>], a: I) -> PotentialVertex<I, W> {
+        let (root, _) = find_potential(representative, a);
+        representative[I::usize(root)]
+    }
+
+    fn try_find(
+        representative: &mut [PotentialVertex<I, W>],
+        a: I,
+    ) -> Result<PotentialVertex<I, W>, UnionFindError> {
+        // the path is validated without touching it before it gets compressed
+        NoCompression::try_find(representative, a)?;
+        Ok(Self::find(representative, a))
+    }
+}
+
+impl<H, T> SetSize<T> for Potential<H>
+where
+    T: VertexType,
+    QuickUnionWith<H, NoCompression>: SetSize<T>,
+{
+    fn set_size<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &[S],
+        a: T::IdentifierType,
+    ) -> usize {
+        QuickUnionWith::<H, NoCompression>::set_size(representative, heuristic, a)
+    }
+}
+
+impl<H, T> Validate<T> for Potential<H>
+where
+    T: VertexType,
+    H: Heuristic,
+{
+    fn validate<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+    ) -> Result<(), UnionFindError> {
+        QuickUnionWith::<H, NoCompression>::validate(representative, heuristic)
+    }
+}
+
+generate_default_ctor_potential!(u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::{Contradiction, PotentialVertex};
+    use crate::{
+        ByRank, BySize, Counted, Potential, UnionFind, UnionFindError, UnionResult, VertexType,
+    };
+    use core::mem;
+
+    #[test]
+    fn test_potential() {
+        let mut uf = UnionFind::<Potential, PotentialVertex<u8, i32>, 8>::default();
+        assert_eq!(Ok(true), uf.union_with(0, 1, 5).map(|r| r.is_merged()));
+        assert_eq!(Ok(true), uf.union_with(2, 1, -3).map(|r| r.is_merged()));
+        assert_eq!(Ok(true), uf.union_with(3, 4, 10).map(|r| r.is_merged()));
+        assert_eq!(Ok(true), uf.union_with(4, 2, 1).map(|r| r.is_merged()));
+
+        assert_eq!(Some(5), uf.diff(0, 1));
+        assert_eq!(Some(-5), uf.diff(1, 0));
+        assert_eq!(Some(8), uf.diff(0, 2));
+        assert_eq!(Some(7), uf.diff(0, 4));
+        assert_eq!(Some(3), uf.diff(3, 0));
+        assert_eq!(Some(0), uf.diff(6, 6));
+        assert_eq!(None, uf.diff(0, 7));
+        assert!(uf.connected(3, 1));
+        assert_eq!(4, uf.count());
+    }
+
+    #[test]
+    fn test_potential_contradiction() {
+        let mut uf = UnionFind::<Potential<BySize>, PotentialVertex<u16, i64>, 4>::default();
+        uf.union_with(0, 1, 2).unwrap();
+        uf.union_with(1, 2, 3).unwrap();
+        assert_eq!(
+            Ok(UnionResult::AlreadyConnected {
+                root: uf.find(0).id()
+            }),
+            uf.union_with(0, 2, 5)
+        );
+        assert_eq!(Err(Contradiction { diff: 5 }), uf.union_with(0, 2, 4));
+        assert_eq!(Some(5), uf.diff(0, 2));
+        assert_eq!(3, uf.set_size(2));
+    }
+
+    #[test]
+    fn test_potential_parity() {
+        let mut uf = UnionFind::<Potential, PotentialVertex<u8, bool>, 5>::default();
+        uf.union_with(0, 1, true).unwrap();
+        uf.union_with(1, 2, true).unwrap();
+        uf.union_with(3, 4, false).unwrap();
+        assert_eq!(Some(false), uf.diff(0, 2));
+        assert_eq!(Some(true), uf.diff(2, 1));
+        assert!(uf.union_with(2, 0, true).is_err());
+        assert!(uf.union_with(4, 2, true).is_ok());
+        assert_eq!(Some(true), uf.diff(3, 0));
+    }
+
+    #[test]
+    fn test_potential_slice() {
+        let mut representative = [
+            PotentialVertex::new(0u8),
+            PotentialVertex {
+                parent: 0,
+                potential: 4,
+            },
+            PotentialVertex::new(2),
+        ];
+        let mut heuristic = [1, 0, 0];
+        let mut uf =
+            UnionFind::<Potential<ByRank<true>>, _, 3>::new(&mut representative, &mut heuristic);
+        assert_eq!(Some(-4), uf.diff(0, 1));
+        uf.union_with(2, 1, 1).unwrap();
+        assert_eq!(Some(5), uf.diff(2, 0));
+        assert_eq!(
+            PotentialVertex {
+                parent: 0,
+                potential: 5
+            },
+            representative[2]
+        );
+    }
+
+    #[test]
+    fn test_potential_counted() {
+        let mut uf = UnionFind::<Counted<Potential>, PotentialVertex<u32, i16>, 6>::default();
+        uf.union_with(5, 4, 1).unwrap();
+        uf.union_with(4, 3, 1).unwrap();
+        uf.union_sets(0, 1);
+        assert_eq!(Some(2), uf.diff(5, 3));
+        assert_eq!(Some(0), uf.diff(0, 1));
+        assert_eq!(3, uf.count());
+    }
+
+    #[test]
+    fn test_potential_try() {
+        let mut uf = UnionFind::<Potential, PotentialVertex<u8, i8>, 4>::default();
+        assert_eq!(
+            Err(UnionFindError::OutOfBounds { id: 4, len: 4 }),
+            uf.try_union_sets(0, 4)
+        );
+        assert!(uf.try_union_sets(0, 3).is_ok());
+        assert_eq!(Ok(true), uf.try_connected(3, 0));
+    }
+
+    #[test]
+    fn test_potential_sz() {
+        assert_eq!(
+            mem::size_of::<[PotentialVertex<u32, i32>; 10]>() + mem::size_of::<[usize; 10]>(),
+            mem::size_of::<UnionFind::<'_, Potential, PotentialVertex<u32, i32>, 10>>()
+        );
+    }
+}
@@ -1,6 +1,9 @@
 //! Quick Find implementations
 
-use crate::{AlgorithmContainer, Connected, Find, Union, UnionFind, VertexType};
+use crate::{
+    AlgorithmContainer, Connected, Find, HeuristicType, SetSize, Union, UnionFind, UnionFindError,
+    UnionResult, Validate, VertexType,
+};
 
 /// [`QuickFind`] algorithm
 #[derive(Debug, Default)]
@@ -9,11 +12,17 @@ pub struct QuickFind<const IS_SLICE: bool = false>;
 impl AlgorithmContainer for QuickFind<false> {
     type HeuristicContainer<'a, const N: usize> = [usize; 0];
     type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = [R; N];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
 }
 
 impl AlgorithmContainer for QuickFind<true> {
     type HeuristicContainer<'a, const N: usize> = [usize; 0];
     type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = &'a mut [R];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
 }
 
 macro_rules! generate_default_ctor_quickfind {
@@ -31,6 +40,9 @@ macro_rules! generate_default_ctor_quickfind {
                 Self {
                     representative,
                     heuristic: [0; 0],
+                    siblings: [],
+                    components: [0; 0],
+                    history: (),
                     algorithm: Default::default(),
                 }
             }
@@ -47,6 +59,9 @@ where
         Self {
             representative,
             heuristic: [0; 0],
+            siblings: [],
+            components: [0; 0],
+            history: (),
             algorithm: Default::default(),
         }
     }
@@ -60,6 +75,14 @@ where
     fn connected(representative: &mut [T], a: T::IdentifierType, b: T::IdentifierType) -> bool {
         Self::find(representative, a) == Self::find(representative, b)
     }
+
+    fn try_connected(
+        representative: &mut [T],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<bool, UnionFindError> {
+        Ok(Self::try_find(representative, a)? == Self::try_find(representative, b)?)
+    }
 }
 
 impl<T, const IS_SLICE: bool> Union<T> for QuickFind<IS_SLICE>
@@ -67,19 +90,39 @@ where
     T: VertexType,
     Self: Find<T>,
 {
-    fn union_sets(
+    fn union_sets<S: HeuristicType>(
         representative: &mut [T],
-        _heuristic: &mut [usize],
+        _heuristic: &mut [S],
         a: T::IdentifierType,
         b: T::IdentifierType,
-    ) {
+    ) -> UnionResult<T::IdentifierType> {
         let root_a = Self::find(representative, a);
         let root_b = Self::find(representative, b);
+        if root_a == root_b {
+            return UnionResult::AlreadyConnected { root: root_b.id() };
+        }
+
         for item in representative {
             if *item == root_a {
                 *item = root_b;
             }
         }
+
+        UnionResult::Merged {
+            root: root_b.id(),
+            absorbed: root_a.id(),
+        }
+    }
+
+    fn try_union_sets<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<UnionResult<T::IdentifierType>, UnionFindError> {
+        Self::try_find(representative, a)?;
+        Self::try_find(representative, b)?;
+        Ok(Self::union_sets(representative, heuristic, a, b))
     }
 }
 
@@ -91,13 +134,65 @@ where
         assert!(T::usize(a) < representative.len());
         representative[T::usize(a)]
     }
+
+    fn try_find(representative: &mut [T], a: T::IdentifierType) -> Result<T, UnionFindError> {
+        let len = representative.len();
+        let root = *representative
+            .get(T::usize(a))
+            .ok_or(UnionFindError::OutOfBounds {
+                id: T::usize(a),
+                len,
+            })?;
+
+        if T::usize(root.id()) >= len {
+            return Err(UnionFindError::CorruptedParent { id: T::usize(a) });
+        }
+
+        Ok(root)
+    }
+}
+
+impl<T, const IS_SLICE: bool> SetSize<T> for QuickFind<IS_SLICE>
+where
+    T: VertexType,
+    Self: Find<T>,
+{
+    fn set_size<S: HeuristicType>(
+        representative: &mut [T],
+        _heuristic: &[S],
+        a: T::IdentifierType,
+    ) -> usize {
+        let root = Self::find(representative, a);
+        representative.iter().filter(|&&item| item == root).count()
+    }
+}
+
+impl<T, const IS_SLICE: bool> Validate<T> for QuickFind<IS_SLICE>
+where
+    T: VertexType,
+{
+    /// Every node points straight at a root
+    fn validate<S: HeuristicType>(
+        representative: &mut [T],
+        _heuristic: &mut [S],
+    ) -> Result<(), UnionFindError> {
+        let len = representative.len();
+        for (i, node) in representative.iter().enumerate() {
+            let root = T::usize(node.id());
+            if root >= len || T::usize(representative[root].id()) != root {
+                return Err(UnionFindError::CorruptedParent { id: i });
+            }
+        }
+
+        Ok(())
+    }
 }
 
 generate_default_ctor_quickfind!(u8, u16, u32, u64, usize);
 
 #[cfg(test)]
 mod tests {
-    use crate::{tests::CityVertex, QuickFind, UnionFind};
+    use crate::{tests::CityVertex, QuickFind, UnionFind, UnionFindError, UnionResult};
     use core::{mem, panic};
 
     #[test]
@@ -121,12 +216,84 @@ mod tests {
         assert!(uf.connected(3, 9));
     }
 
+    #[test]
+    fn test_qf_union_result() {
+        let mut uf = UnionFind::<QuickFind, u32, 10>::default();
+        assert_eq!(
+            UnionResult::Merged {
+                root: 3,
+                absorbed: 4
+            },
+            uf.union_sets(4, 3)
+        );
+        assert_eq!(
+            UnionResult::Merged {
+                root: 8,
+                absorbed: 3
+            },
+            uf.union_sets(3, 8)
+        );
+        assert_eq!(
+            UnionResult::AlreadyConnected { root: 8 },
+            uf.union_sets(4, 8)
+        );
+    }
+
+    #[test]
+    fn test_qf_set_size() {
+        let mut uf = UnionFind::<QuickFind, u32, 10>::default();
+        uf.union_sets(4, 3);
+        uf.union_sets(3, 8);
+        uf.union_sets(6, 5);
+        assert_eq!(3, uf.set_size(4));
+        assert_eq!(2, uf.set_size(5));
+        assert_eq!(1, uf.set_size(0));
+    }
+
+    #[test]
+    fn test_qf_try() {
+        let mut uf = UnionFind::<QuickFind, u32, 10>::default();
+        assert_eq!(
+            Ok(UnionResult::Merged {
+                root: 3,
+                absorbed: 4
+            }),
+            uf.try_union_sets(4, 3)
+        );
+        assert_eq!(Ok(true), uf.try_connected(3, 4));
+        assert_eq!(
+            Err(UnionFindError::OutOfBounds { id: 10, len: 10 }),
+            uf.try_union_sets(4, 10)
+        );
+        assert_eq!(
+            Err(UnionFindError::OutOfBounds { id: 42, len: 10 }),
+            uf.try_connected(42, 3)
+        );
+        assert_eq!(Ok(3), uf.try_find(4));
+    }
+
+    #[test]
+    fn test_qf_try_corrupted() {
+        let mut representative = [0, 1, 7, 3];
+        let mut uf = UnionFind::<QuickFind<true>, u32, 4>::new(&mut representative);
+        assert_eq!(
+            Err(UnionFindError::CorruptedParent { id: 2 }),
+            uf.try_find(2)
+        );
+        assert_eq!(
+            Err(UnionFindError::CorruptedParent { id: 2 }),
+            uf.try_union_sets(2, 3)
+        );
+        assert_eq!([0, 1, 7, 3], representative);
+    }
+
     impl<'a, const N: usize> TryFrom<[CityVertex<'a>; N]>
         for UnionFind<'_, QuickFind, CityVertex<'a>, N>
     {
         type Error = &'static str;
 
         fn try_from(cities: [CityVertex<'a>; N]) -> Result<Self, Self::Error> {
+            #[allow(clippy::needless_range_loop)]
             for id in 0..N {
                 if cities[id].id as usize != id {
                     return Err("Invalid cities id!");
@@ -136,6 +303,9 @@ mod tests {
             Ok(Self {
                 representative: cities,
                 heuristic: [0; 0],
+                siblings: [],
+                components: [0; 0],
+                history: (),
                 algorithm: Default::default(),
             })
         }
@@ -178,6 +348,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::never_loop)]
     fn test_getter() {
         let mut uf = UnionFind::<QuickFind, u32, 10>::default();
         uf.union_sets(4, 3);
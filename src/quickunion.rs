@@ -2,7 +2,10 @@
 
 use core::marker::PhantomData;
 
-use crate::{AlgorithmContainer, Connected, Find, Union, UnionFind, VertexType};
+use crate::{
+    scan_set_size, validate_forest, validate_parents, AlgorithmContainer, Connected, Find,
+    HeuristicType, SetSize, Union, UnionFind, UnionFindError, UnionResult, Validate, VertexType,
+};
 
 /// Link by rank of tree
 #[derive(Default, Debug)]
@@ -18,115 +21,542 @@ pub struct Unweighted<const IS_SLICE: bool = false>;
 
 /// Heuristic for quick union algorithm
 pub trait Heuristic {
-    fn handle_decision<T>(
+    /// Links roots `a` and `b`, returning which root won
+    fn handle_decision<T, S>(
         a: T::IdentifierType,
         b: T::IdentifierType,
-        heuristic: &mut [usize],
+        heuristic: &mut [S],
         representative: &mut [T],
-    ) where
-        T: VertexType;
+    ) -> UnionResult<T::IdentifierType>
+    where
+        T: VertexType,
+        S: HeuristicType;
+
+    /// Fallible version of [`Heuristic::handle_decision`]. `a` and `b` must be roots
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError::HeuristicOutOfBounds`] if either root does not index into `heuristic`
+    fn try_handle_decision<T, S>(
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+        heuristic: &mut [S],
+        representative: &mut [T],
+    ) -> Result<UnionResult<T::IdentifierType>, UnionFindError>
+    where
+        T: VertexType,
+        S: HeuristicType,
+    {
+        for id in [T::usize(a), T::usize(b)] {
+            if id >= heuristic.len() {
+                return Err(UnionFindError::HeuristicOutOfBounds {
+                    id,
+                    len: heuristic.len(),
+                });
+            }
+        }
+
+        Ok(Self::handle_decision(a, b, heuristic, representative))
+    }
+
+    /// Size of the component rooted at `root`, if the heuristic keeps track of it
+    fn size<T, S>(_root: T::IdentifierType, _heuristic: &[S]) -> Option<usize>
+    where
+        T: VertexType,
+        S: HeuristicType,
+    {
+        None
+    }
+
+    /// Checks that `representative` is a forest and that `heuristic` matches it, every parent
+    /// in `representative` must be in bounds
+    ///
+    /// The default only checks the forest, compressing every path on the way
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError`] for the first node that is on a cycle or whose heuristic does
+    /// not match
+    fn validate<T, S>(representative: &mut [T], _heuristic: &mut [S]) -> Result<(), UnionFindError>
+    where
+        T: VertexType,
+        S: HeuristicType,
+    {
+        validate_forest(representative)
+    }
+}
+
+/// Largest rank union by rank can reach with `len` nodes, i.e. `lg len`
+pub(crate) fn max_rank(len: usize) -> usize {
+    len.checked_ilog2().unwrap_or(0) as usize
+}
+
+/// Checks that the heuristic buffer covers every node
+fn check_heuristic_len<T, S>(representative: &[T], heuristic: &[S]) -> Result<(), UnionFindError> {
+    if heuristic.len() < representative.len() {
+        return Err(UnionFindError::HeuristicOutOfBounds {
+            id: heuristic.len(),
+            len: heuristic.len(),
+        });
+    }
+
+    Ok(())
 }
 
 impl<const IS_SLICE: bool> Heuristic for Unweighted<IS_SLICE> {
     #[inline(always)]
-    fn handle_decision<T>(
+    fn handle_decision<T, S>(
         a: T::IdentifierType,
         b: T::IdentifierType,
-        _heuristic: &mut [usize],
+        _heuristic: &mut [S],
         representative: &mut [T],
-    ) where
+    ) -> UnionResult<T::IdentifierType>
+    where
         T: VertexType,
+        S: HeuristicType,
     {
         if a == b {
-            return;
+            return UnionResult::AlreadyConnected { root: a };
         }
 
         representative[T::usize(a)] = representative[T::usize(b)];
+        UnionResult::Merged {
+            root: b,
+            absorbed: a,
+        }
+    }
+
+    #[inline(always)]
+    fn try_handle_decision<T, S>(
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+        heuristic: &mut [S],
+        representative: &mut [T],
+    ) -> Result<UnionResult<T::IdentifierType>, UnionFindError>
+    where
+        T: VertexType,
+        S: HeuristicType,
+    {
+        Ok(Self::handle_decision(a, b, heuristic, representative))
     }
 }
 
 impl<const IS_SLICE: bool> Heuristic for ByRank<IS_SLICE> {
     #[inline(always)]
-    fn handle_decision<T>(
+    fn handle_decision<T, S>(
         mut a: T::IdentifierType,
         mut b: T::IdentifierType,
-        rank: &mut [usize],
+        rank: &mut [S],
         representative: &mut [T],
-    ) where
+    ) -> UnionResult<T::IdentifierType>
+    where
         T: VertexType,
+        S: HeuristicType,
     {
-        if a != b {
-            if rank[T::usize(a)] < rank[T::usize(b)] {
-                core::mem::swap(&mut a, &mut b);
-            }
-            representative[T::usize(b)] = representative[T::usize(a)];
-            if rank[T::usize(a)] == rank[T::usize(b)] {
-                rank[T::usize(a)] += 1;
+        if a == b {
+            return UnionResult::AlreadyConnected { root: a };
+        }
+
+        if rank[T::usize(a)].get() < rank[T::usize(b)].get() {
+            core::mem::swap(&mut a, &mut b);
+        }
+        representative[T::usize(b)] = representative[T::usize(a)];
+        let (root_rank, absorbed_rank) = (rank[T::usize(a)].get(), rank[T::usize(b)].get());
+        if root_rank == absorbed_rank {
+            rank[T::usize(a)].set(root_rank + 1);
+        }
+
+        UnionResult::Merged {
+            root: a,
+            absorbed: b,
+        }
+    }
+
+    /// Ranks strictly increase towards the root and never exceed `lg N`
+    fn validate<T, S>(representative: &mut [T], rank: &mut [S]) -> Result<(), UnionFindError>
+    where
+        T: VertexType,
+        S: HeuristicType,
+    {
+        check_heuristic_len(representative, rank)?;
+
+        // rising ranks rule out cycles, and a root of rank `r` has at least `2^r` nodes, which
+        // also keeps later unions from overflowing a rank
+        let max_rank = max_rank(representative.len());
+        for (i, node) in representative.iter().enumerate() {
+            let parent = T::usize(node.id());
+            if rank[i].get() > max_rank || (parent != i && rank[i].get() >= rank[parent].get()) {
+                return Err(UnionFindError::InconsistentHeuristic { id: i });
             }
         }
+
+        Ok(())
     }
 }
 
 impl<const IS_SLICE: bool> Heuristic for BySize<IS_SLICE> {
     #[inline(always)]
-    fn handle_decision<T>(
+    fn handle_decision<T, S>(
         mut a: T::IdentifierType,
         mut b: T::IdentifierType,
-        size: &mut [usize],
+        size: &mut [S],
         representative: &mut [T],
-    ) where
+    ) -> UnionResult<T::IdentifierType>
+    where
+        T: VertexType,
+        S: HeuristicType,
+    {
+        if a == b {
+            return UnionResult::AlreadyConnected { root: a };
+        }
+
+        if size[T::usize(a)].get() < size[T::usize(b)].get() {
+            core::mem::swap(&mut a, &mut b);
+        }
+        representative[T::usize(b)] = representative[T::usize(a)];
+        let merged = size[T::usize(a)].get() + size[T::usize(b)].get();
+        size[T::usize(a)].set(merged);
+
+        UnionResult::Merged {
+            root: a,
+            absorbed: b,
+        }
+    }
+
+    #[inline(always)]
+    fn size<T, S>(root: T::IdentifierType, size: &[S]) -> Option<usize>
+    where
         T: VertexType,
+        S: HeuristicType,
     {
-        if a != b {
-            if size[T::usize(a)] < size[T::usize(b)] {
-                core::mem::swap(&mut a, &mut b);
+        Some(size[T::usize(root)].get())
+    }
+
+    /// Sizes at least double towards the root and every root holds the number of nodes in its
+    /// component
+    fn validate<T, S>(representative: &mut [T], size: &mut [S]) -> Result<(), UnionFindError>
+    where
+        T: VertexType,
+        S: HeuristicType,
+    {
+        check_heuristic_len(representative, size)?;
+
+        // a node is absorbed by a root at least as large and sizes only grow afterwards, so
+        // doubling sizes rule out cycles and keep every path within `lg N` edges
+        let len = representative.len();
+        for (i, node) in representative.iter().enumerate() {
+            let parent = T::usize(node.id());
+            let (own, parent_size) = (size[i].get(), size[parent].get());
+            if !(1..=len).contains(&own) || (parent != i && 2 * own > parent_size) {
+                return Err(UnionFindError::InconsistentHeuristic { id: i });
             }
-            representative[T::usize(b)] = representative[T::usize(a)];
-            size[T::usize(a)] += size[T::usize(b)];
+        }
+
+        // counting every node off its root leaves 0 on consistent roots, adding them back
+        // afterwards restores the buffer whatever the outcome, even if values wrap around at a
+        // narrower width
+        for i in 0..representative.len() {
+            let root = T::usize(NoCompression::find(representative, representative[i].id()).id());
+            size[root].set(size[root].get().wrapping_sub(1));
+        }
+
+        let inconsistent = (0..representative.len())
+            .find(|&i| T::usize(representative[i].id()) == i && size[i].get() != 0);
+
+        for i in 0..representative.len() {
+            let root = T::usize(NoCompression::find(representative, representative[i].id()).id());
+            size[root].set(size[root].get().wrapping_add(1));
+        }
+
+        match inconsistent {
+            Some(id) => Err(UnionFindError::InconsistentHeuristic { id }),
+            None => Ok(()),
         }
     }
 }
 
-/// [`QuickUnion`] algorithm
+/// Path compression strategy applied by [`QuickUnionWith`] during find operation
+pub trait Compression {
+    /// Finds the root of `a`, flattening the path on the way
+    fn find<T>(representative: &mut [T], a: T::IdentifierType) -> T
+    where
+        T: VertexType;
+
+    /// Fallible version of [`Compression::find`]
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError`] if the node is out of bounds or the buffer is corrupted
+    fn try_find<T>(representative: &mut [T], a: T::IdentifierType) -> Result<T, UnionFindError>
+    where
+        T: VertexType;
+}
+
+/// Leaves the path untouched
+#[derive(Default, Debug)]
+pub struct NoCompression;
+
+/// Points every other node on the path to its grandparent in a single pass
+#[derive(Default, Debug)]
+pub struct PathHalving;
+
+/// Points every node on the path to its grandparent in a single pass
+#[derive(Default, Debug)]
+pub struct PathSplitting;
+
+/// Points every node on the path to the root, using a second pass
+#[derive(Default, Debug)]
+pub struct FullCompression;
+
+/// Checks that `a` indexes into `representative`
+#[inline(always)]
+fn check_bounds<T>(representative: &[T], a: T::IdentifierType) -> Result<(), UnionFindError>
+where
+    T: VertexType,
+{
+    if T::usize(a) >= representative.len() {
+        return Err(UnionFindError::OutOfBounds {
+            id: T::usize(a),
+            len: representative.len(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Gets the parent of `a`, which must be in bounds, checking that the parent is in bounds as well
+#[inline(always)]
+fn try_parent<T>(representative: &[T], a: T::IdentifierType) -> Result<T, UnionFindError>
+where
+    T: VertexType,
+{
+    let parent = representative[T::usize(a)];
+    if T::usize(parent.id()) >= representative.len() {
+        return Err(UnionFindError::CorruptedParent { id: T::usize(a) });
+    }
+
+    Ok(parent)
+}
+
+impl Compression for NoCompression {
+    #[inline(always)]
+    fn find<T>(representative: &mut [T], mut a: T::IdentifierType) -> T
+    where
+        T: VertexType,
+    {
+        while a != representative[T::usize(a)].id() {
+            a = representative[T::usize(a)].id()
+        }
+        representative[T::usize(a)]
+    }
+
+    fn try_find<T>(representative: &mut [T], mut a: T::IdentifierType) -> Result<T, UnionFindError>
+    where
+        T: VertexType,
+    {
+        check_bounds(representative, a)?;
+
+        // a valid path visits every node at most once
+        for _ in 0..representative.len() {
+            let parent = try_parent(representative, a)?;
+            if a == parent.id() {
+                return Ok(parent);
+            }
+            a = parent.id();
+        }
+
+        Err(UnionFindError::CorruptedParent { id: T::usize(a) })
+    }
+}
+
+impl Compression for PathHalving {
+    #[inline(always)]
+    fn find<T>(representative: &mut [T], mut a: T::IdentifierType) -> T
+    where
+        T: VertexType,
+    {
+        while a != representative[T::usize(a)].id() {
+            representative[T::usize(a)] =
+                representative[T::usize(representative[T::usize(a)].id())];
+            a = representative[T::usize(a)].id()
+        }
+        representative[T::usize(a)]
+    }
+
+    fn try_find<T>(representative: &mut [T], mut a: T::IdentifierType) -> Result<T, UnionFindError>
+    where
+        T: VertexType,
+    {
+        check_bounds(representative, a)?;
+
+        // a valid path visits every node at most once
+        for _ in 0..representative.len() {
+            let parent = try_parent(representative, a)?;
+            if a == parent.id() {
+                return Ok(parent);
+            }
+
+            representative[T::usize(a)] = try_parent(representative, parent.id())?;
+            a = representative[T::usize(a)].id();
+        }
+
+        Err(UnionFindError::CorruptedParent { id: T::usize(a) })
+    }
+}
+
+impl Compression for PathSplitting {
+    #[inline(always)]
+    fn find<T>(representative: &mut [T], mut a: T::IdentifierType) -> T
+    where
+        T: VertexType,
+    {
+        while a != representative[T::usize(a)].id() {
+            let parent = representative[T::usize(a)].id();
+            representative[T::usize(a)] = representative[T::usize(parent)];
+            a = parent;
+        }
+        representative[T::usize(a)]
+    }
+
+    fn try_find<T>(representative: &mut [T], mut a: T::IdentifierType) -> Result<T, UnionFindError>
+    where
+        T: VertexType,
+    {
+        check_bounds(representative, a)?;
+
+        // a valid path visits every node at most once
+        for _ in 0..representative.len() {
+            let parent = try_parent(representative, a)?;
+            if a == parent.id() {
+                return Ok(parent);
+            }
+
+            representative[T::usize(a)] = try_parent(representative, parent.id())?;
+            a = parent.id();
+        }
+
+        Err(UnionFindError::CorruptedParent { id: T::usize(a) })
+    }
+}
+
+impl Compression for FullCompression {
+    #[inline(always)]
+    fn find<T>(representative: &mut [T], a: T::IdentifierType) -> T
+    where
+        T: VertexType,
+    {
+        let root = NoCompression::find(representative, a);
+        compress_to(representative, a, root);
+        root
+    }
+
+    fn try_find<T>(representative: &mut [T], a: T::IdentifierType) -> Result<T, UnionFindError>
+    where
+        T: VertexType,
+    {
+        let root = NoCompression::try_find(representative, a)?;
+        compress_to(representative, a, root);
+        Ok(root)
+    }
+}
+
+/// Points every node on the path from `a` to `root`, the path must be valid
+#[inline(always)]
+fn compress_to<T>(representative: &mut [T], mut a: T::IdentifierType, root: T)
+where
+    T: VertexType,
+{
+    while a != root.id() {
+        let parent = representative[T::usize(a)].id();
+        representative[T::usize(a)] = root;
+        a = parent;
+    }
+}
+
+/// Maps the `COMPRESS_PATH` flag of [`QuickUnion`] onto a [`Compression`] strategy
+#[derive(Default, Debug)]
+pub struct CompressPath<const COMPRESS_PATH: bool>;
+
+/// Selects the [`Compression`] strategy for a flag
+pub trait CompressionFlag {
+    type Strategy: Compression;
+}
+
+impl CompressionFlag for CompressPath<true> {
+    type Strategy = PathHalving;
+}
+
+impl CompressionFlag for CompressPath<false> {
+    type Strategy = NoCompression;
+}
+
+/// [`QuickUnionWith`] algorithm
 ///
 /// This algorithm is parameterized by the following
 /// - `H` - Heuristic Type. Available types: [`ByRank`], [`BySize`], [`Unweighted`]
-/// - `COMPRESS_PATH` - boolean value, enables path compression during find operation
+/// - `C` - Compression Type. Available types: [`NoCompression`], [`PathHalving`], [`PathSplitting`], [`FullCompression`]
 ///
-/// By default, [`ByRank`] heuristic is used and path compression is enabled
+/// By default, [`ByRank`] heuristic is used with [`PathHalving`]
 #[derive(Debug, Default)]
-pub struct QuickUnion<H = ByRank, const COMPRESS_PATH: bool = true> {
+pub struct QuickUnionWith<H = ByRank, C = PathHalving> {
     heuristic: PhantomData<H>,
+    compression: PhantomData<C>,
 }
 
-impl AlgorithmContainer for QuickUnion<ByRank> {
+/// [`QuickUnion`] algorithm
+///
+/// This algorithm is parameterized by the following
+/// - `H` - Heuristic Type. Available types: [`ByRank`], [`BySize`], [`Unweighted`]
+/// - `COMPRESS_PATH` - boolean value, enables path compression during find operation
+///
+/// By default, [`ByRank`] heuristic is used and path compression is enabled.
+/// Path compression is done by [`PathHalving`], use [`QuickUnionWith`] to pick another strategy
+pub type QuickUnion<H = ByRank, const COMPRESS_PATH: bool = true> =
+    QuickUnionWith<H, <CompressPath<COMPRESS_PATH> as CompressionFlag>::Strategy>;
+
+impl<C: Compression> AlgorithmContainer for QuickUnionWith<ByRank, C> {
     type HeuristicContainer<'a, const N: usize> = [usize; N];
     type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = [R; N];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
 }
 
-impl AlgorithmContainer for QuickUnion<BySize> {
+impl<C: Compression> AlgorithmContainer for QuickUnionWith<BySize, C> {
     type HeuristicContainer<'a, const N: usize> = [usize; N];
     type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = [R; N];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
 }
 
-impl<const PATH_COMPRESS: bool> AlgorithmContainer for QuickUnion<Unweighted, PATH_COMPRESS> {
+impl<C: Compression> AlgorithmContainer for QuickUnionWith<Unweighted, C> {
     type HeuristicContainer<'a, const N: usize> = [usize; 0];
     type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = [R; N];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
 }
 
-impl<const PATH_COMPRESS: bool> AlgorithmContainer for QuickUnion<Unweighted<true>, PATH_COMPRESS> {
+impl<C: Compression> AlgorithmContainer for QuickUnionWith<Unweighted<true>, C> {
     type HeuristicContainer<'a, const N: usize> = [usize; 0];
     type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = &'a mut [R];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
 }
 
-impl AlgorithmContainer for QuickUnion<BySize<true>> {
+impl<C: Compression> AlgorithmContainer for QuickUnionWith<BySize<true>, C> {
     type HeuristicContainer<'a, const N: usize> = &'a mut [usize];
     type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = &'a mut [R];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
 }
 
-impl AlgorithmContainer for QuickUnion<ByRank<true>> {
+impl<C: Compression> AlgorithmContainer for QuickUnionWith<ByRank<true>, C> {
     type HeuristicContainer<'a, const N: usize> = &'a mut [usize];
     type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = &'a mut [R];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
 }
 
 macro_rules! generate_representative {
@@ -139,38 +569,47 @@ macro_rules! generate_representative {
     }};
 }
 
-/// Macro to generate default constructor for weighted quickunion (by rank) with path compression
+/// Macro to generate default constructor for quickunion with every heuristic and compression
 macro_rules! generate_default_ctor {
     ($($num_type:ident), *) => {
         $(
-        impl<const N: usize> Default for UnionFind<'_, QuickUnion, $num_type, N>
+        impl<C: Compression, const N: usize> Default for UnionFind<'_, QuickUnionWith<ByRank, C>, $num_type, N>
         {
             fn default() -> Self {
                 Self {
                     representative: generate_representative!(N, $num_type),
                     heuristic: [0; N],
+                    siblings: [],
+                    components: [0; 0],
+                    history: (),
                     algorithm: Default::default(),
                 }
             }
         }
 
-        impl<const N: usize> Default for UnionFind<'_, QuickUnion<BySize>, $num_type, N>
+        impl<C: Compression, const N: usize> Default for UnionFind<'_, QuickUnionWith<BySize, C>, $num_type, N>
         {
             fn default() -> Self {
                 Self {
                     representative: generate_representative!(N, $num_type),
                     heuristic: [1; N],
+                    siblings: [],
+                    components: [0; 0],
+                    history: (),
                     algorithm: Default::default(),
                 }
             }
         }
 
-        impl<const N: usize, const PATH_COMPRESS: bool> Default for UnionFind<'_, QuickUnion<Unweighted, PATH_COMPRESS>, $num_type, N>
+        impl<C: Compression, const N: usize> Default for UnionFind<'_, QuickUnionWith<Unweighted, C>, $num_type, N>
         {
             fn default() -> Self {
                 Self {
                     representative: generate_representative!(N, $num_type),
                     heuristic: [0; 0],
+                    siblings: [],
+                    components: [0; 0],
+                    history: (),
                     algorithm: Default::default(),
                 }
             }
@@ -179,50 +618,67 @@ macro_rules! generate_default_ctor {
     };
 }
 
-impl<'a, T, const N: usize> UnionFind<'a, QuickUnion<BySize<true>>, T, N>
+impl<'a, T, C, const N: usize> UnionFind<'a, QuickUnionWith<BySize<true>, C>, T, N>
 where
     T: VertexType,
+    C: Compression,
 {
     pub fn new(representative: &'a mut [T], heuristic: &'a mut [usize]) -> Self {
         Self {
             representative,
             heuristic,
+            siblings: [],
+            components: [0; 0],
+            history: (),
             algorithm: Default::default(),
         }
     }
 }
 
-impl<'a, T, const N: usize> UnionFind<'a, QuickUnion<ByRank<true>>, T, N>
+impl<'a, T, C, const N: usize> UnionFind<'a, QuickUnionWith<ByRank<true>, C>, T, N>
 where
     T: VertexType,
+    C: Compression,
 {
     pub fn new(representative: &'a mut [T], heuristic: &'a mut [usize]) -> Self {
-        debug_assert!(representative.len() >= N, "Representative slice must have at least len >= N!");
-        debug_assert!(heuristic.len() >= N, "Heuristic slice must have at least len >= N!");
+        debug_assert!(
+            representative.len() >= N,
+            "Representative slice must have at least len >= N!"
+        );
+        debug_assert!(
+            heuristic.len() >= N,
+            "Heuristic slice must have at least len >= N!"
+        );
 
         Self {
             representative,
             heuristic,
+            siblings: [],
+            components: [0; 0],
+            history: (),
             algorithm: Default::default(),
         }
     }
 }
 
-impl<'a, T, const N: usize, const PATH_COMPRESS: bool>
-    UnionFind<'a, QuickUnion<Unweighted<true>, PATH_COMPRESS>, T, N>
+impl<'a, T, C, const N: usize> UnionFind<'a, QuickUnionWith<Unweighted<true>, C>, T, N>
 where
     T: VertexType,
+    C: Compression,
 {
     pub fn new(representative: &'a mut [T]) -> Self {
         Self {
             representative,
             heuristic: [0; 0],
+            siblings: [],
+            components: [0; 0],
+            history: (),
             algorithm: Default::default(),
         }
     }
 }
 
-impl<H, T, const PATH_COMPRESS: bool> Connected<T> for QuickUnion<H, PATH_COMPRESS>
+impl<H, C, T> Connected<T> for QuickUnionWith<H, C>
 where
     T: VertexType,
     Self: Find<T>,
@@ -230,40 +686,89 @@ where
     fn connected(representative: &mut [T], a: T::IdentifierType, b: T::IdentifierType) -> bool {
         Self::find(representative, a) == Self::find(representative, b)
     }
+
+    fn try_connected(
+        representative: &mut [T],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<bool, UnionFindError> {
+        Ok(Self::try_find(representative, a)? == Self::try_find(representative, b)?)
+    }
 }
 
-impl<H, T, const COMPRESS_PATH: bool> Union<T> for QuickUnion<H, COMPRESS_PATH>
+impl<H, C, T> Union<T> for QuickUnionWith<H, C>
 where
     T: VertexType,
     H: Heuristic,
     Self: Find<T>,
 {
-    fn union_sets(
+    fn union_sets<S: HeuristicType>(
         representative: &mut [T],
-        heuristic: &mut [usize],
+        heuristic: &mut [S],
         mut a: T::IdentifierType,
         mut b: T::IdentifierType,
-    ) {
+    ) -> UnionResult<T::IdentifierType> {
         a = Self::find(representative, a).id();
         b = Self::find(representative, b).id();
         H::handle_decision(a, b, heuristic, representative)
     }
+
+    fn try_union_sets<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+        mut a: T::IdentifierType,
+        mut b: T::IdentifierType,
+    ) -> Result<UnionResult<T::IdentifierType>, UnionFindError> {
+        a = Self::try_find(representative, a)?.id();
+        b = Self::try_find(representative, b)?.id();
+        H::try_handle_decision(a, b, heuristic, representative)
+    }
 }
 
-impl<H, T, const COMPRESS_PATH: bool> Find<T> for QuickUnion<H, COMPRESS_PATH>
+impl<H, C, T> Find<T> for QuickUnionWith<H, C>
 where
     T: VertexType,
+    C: Compression,
 {
-    fn find(representative: &mut [T], mut a: T::IdentifierType) -> T {
-        while a != representative[T::usize(a)].id() {
-            // path compression
-            if COMPRESS_PATH {
-                representative[T::usize(a)] =
-                    representative[T::usize(representative[T::usize(a)].id()).id()];
-            }
-            a = representative[T::usize(a)].id()
+    fn find(representative: &mut [T], a: T::IdentifierType) -> T {
+        C::find(representative, a)
+    }
+
+    fn try_find(representative: &mut [T], a: T::IdentifierType) -> Result<T, UnionFindError> {
+        C::try_find(representative, a)
+    }
+}
+
+impl<H, C, T> SetSize<T> for QuickUnionWith<H, C>
+where
+    T: VertexType,
+    H: Heuristic,
+    Self: Find<T>,
+{
+    fn set_size<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &[S],
+        a: T::IdentifierType,
+    ) -> usize {
+        let root = Self::find(representative, a).id();
+        match H::size::<T, S>(root, heuristic) {
+            Some(size) => size,
+            None => scan_set_size::<Self, T>(representative, root),
         }
-        representative[T::usize(a)]
+    }
+}
+
+impl<H, C, T> Validate<T> for QuickUnionWith<H, C>
+where
+    T: VertexType,
+    H: Heuristic,
+{
+    fn validate<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+    ) -> Result<(), UnionFindError> {
+        validate_parents(representative)?;
+        H::validate(representative, heuristic)
     }
 }
 
@@ -271,8 +776,14 @@ generate_default_ctor!(u8, u16, u32, u64, usize);
 
 #[cfg(test)]
 mod tests {
-    use super::{BySize, Heuristic, Unweighted};
-    use crate::{tests::CityVertex, AlgorithmContainer, ByRank, QuickUnion, UnionFind, VertexType};
+    use super::{
+        BySize, FullCompression, Heuristic, NoCompression, PathHalving, PathSplitting,
+        QuickUnionWith, Unweighted,
+    };
+    use crate::{
+        tests::CityVertex, AlgorithmContainer, ByRank, HeuristicType, QuickUnion, UnionFind,
+        UnionFindError, UnionResult, VertexType,
+    };
     use core::mem;
 
     #[test]
@@ -286,6 +797,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::never_loop)]
     fn test_getter_qu() {
         let mut uf = UnionFind::<QuickUnion<Unweighted, false>, u8, 10>::default();
         uf.union_sets(4, 3);
@@ -320,6 +832,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::never_loop)]
     fn test_getter_qupc() {
         let mut uf = UnionFind::<QuickUnion<Unweighted, true>, u8, 10>::default();
         uf.union_sets(4, 3);
@@ -409,11 +922,216 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_union_result() {
+        let mut uf = UnionFind::<QuickUnion<BySize>, u8, 10>::default();
+        assert_eq!(
+            UnionResult::Merged {
+                root: 1,
+                absorbed: 2
+            },
+            uf.union_sets(1, 2)
+        );
+        assert_eq!(
+            UnionResult::Merged {
+                root: 1,
+                absorbed: 3
+            },
+            uf.union_sets(3, 2)
+        );
+        assert_eq!(
+            UnionResult::AlreadyConnected { root: 1 },
+            uf.union_sets(2, 3)
+        );
+
+        let mut uf = UnionFind::<QuickUnion<Unweighted, false>, u8, 10>::default();
+        assert_eq!(
+            UnionResult::Merged {
+                root: 3,
+                absorbed: 4
+            },
+            uf.union_sets(4, 3)
+        );
+        assert_eq!(3, uf.union_sets(9, 4).root());
+    }
+
+    #[test]
+    fn test_set_size() {
+        let mut uf = UnionFind::<QuickUnion<BySize>, u8, 10>::default();
+        uf.union_sets(1, 2);
+        uf.union_sets(2, 3);
+        uf.union_sets(5, 6);
+        assert_eq!(3, uf.set_size(3));
+        assert_eq!(2, uf.set_size(5));
+        assert_eq!(1, uf.set_size(9));
+
+        let mut uf = UnionFind::<QuickUnion, u8, 10>::default();
+        uf.union_sets(1, 2);
+        uf.union_sets(2, 3);
+        uf.union_sets(5, 6);
+        uf.union_sets(3, 6);
+        assert_eq!(5, uf.set_size(1));
+        assert_eq!(1, uf.set_size(0));
+
+        let mut uf = UnionFind::<QuickUnion<Unweighted, false>, u8, 10>::default();
+        uf.union_sets(4, 3);
+        uf.union_sets(3, 8);
+        uf.union_sets(9, 4);
+        assert_eq!(4, uf.set_size(8));
+    }
+
+    #[test]
+    fn test_wqupc_try() {
+        let mut uf = UnionFind::<QuickUnion, u8, 10>::default();
+        assert_eq!(
+            Ok(UnionResult::Merged {
+                root: 1,
+                absorbed: 2
+            }),
+            uf.try_union_sets(1, 2)
+        );
+        assert_eq!(Ok(true), uf.try_connected(2, 1));
+        assert_eq!(Ok(1), uf.try_find(2));
+        assert_eq!(
+            Err(UnionFindError::OutOfBounds { id: 10, len: 10 }),
+            uf.try_union_sets(1, 10)
+        );
+        assert_eq!(
+            Err(UnionFindError::OutOfBounds { id: 255, len: 10 }),
+            uf.try_find(255)
+        );
+    }
+
+    #[test]
+    fn test_try_corrupted() {
+        let mut representative = [0u8, 2, 1, 9];
+        let mut uf =
+            UnionFind::<QuickUnion<Unweighted<true>, false>, u8, 4>::new(&mut representative);
+        assert_eq!(
+            Err(UnionFindError::CorruptedParent { id: 1 }),
+            uf.try_find(1)
+        );
+        assert_eq!(
+            Err(UnionFindError::CorruptedParent { id: 3 }),
+            uf.try_connected(0, 3)
+        );
+        assert_eq!(Ok(0), uf.try_find(0));
+
+        let mut representative = [0u8, 1, 2, 3];
+        let mut heuristic = [0; 2];
+        let mut uf =
+            UnionFind::<QuickUnion<ByRank<true>>, u8, 2>::new(&mut representative, &mut heuristic);
+        assert!(uf.try_union_sets(0, 1).unwrap().is_merged());
+        assert_eq!(
+            Err(UnionFindError::HeuristicOutOfBounds { id: 3, len: 2 }),
+            uf.try_union_sets(0, 3)
+        );
+        assert_eq!([0, 0, 2, 3], representative);
+    }
+
+    #[test]
+    fn test_validate() {
+        // a chain is a valid unweighted forest, checking it points the parents at the root
+        let mut representative: [u16; 1000] = core::array::from_fn(|i| (i + 1).min(999) as u16);
+        let mut uf =
+            UnionFind::<QuickUnion<Unweighted<true>, false>, u16, 1000>::new(&mut representative);
+        assert_eq!(Ok(()), uf.validate());
+        assert_eq!(1, uf.count());
+        assert!(representative[1..].iter().all(|&parent| parent == 999));
+
+        let mut representative = [1u8, 2, 0, 3];
+        let mut uf =
+            UnionFind::<QuickUnion<Unweighted<true>, false>, u8, 4>::new(&mut representative);
+        assert_eq!(
+            Err(UnionFindError::CorruptedParent { id: 2 }),
+            uf.validate()
+        );
+
+        // the root counts every node, but node 1 is not even half the size of its parent
+        let mut representative = [1u8, 2, 2];
+        let mut heuristic = [1, 2, 3];
+        let mut uf =
+            UnionFind::<QuickUnion<BySize<true>>, u8, 3>::new(&mut representative, &mut heuristic);
+        assert_eq!(
+            Err(UnionFindError::InconsistentHeuristic { id: 1 }),
+            uf.validate()
+        );
+        assert_eq!([1, 2, 3], heuristic);
+    }
+
+    #[test]
+    fn test_compression() {
+        // 0 <- 1 <- 2 <- 3 <- 4 <- 5
+        let path = || [0u8, 0, 1, 2, 3, 4];
+
+        let mut representative = path();
+        let uf = &mut UnionFind::<QuickUnionWith<Unweighted<true>, NoCompression>, u8, 6>::new(
+            &mut representative,
+        );
+        assert_eq!(0, uf.find(5));
+        assert_eq!([0, 0, 1, 2, 3, 4], representative);
+
+        let mut representative = path();
+        let uf = &mut UnionFind::<QuickUnionWith<Unweighted<true>, PathHalving>, u8, 6>::new(
+            &mut representative,
+        );
+        assert_eq!(0, uf.find(5));
+        assert_eq!([0, 0, 1, 1, 3, 3], representative);
+
+        let mut representative = path();
+        let uf = &mut UnionFind::<QuickUnionWith<Unweighted<true>, PathSplitting>, u8, 6>::new(
+            &mut representative,
+        );
+        assert_eq!(0, uf.find(5));
+        assert_eq!([0, 0, 0, 1, 2, 3], representative);
+
+        let mut representative = path();
+        let uf = &mut UnionFind::<QuickUnionWith<Unweighted<true>, FullCompression>, u8, 6>::new(
+            &mut representative,
+        );
+        assert_eq!(Ok(0), uf.try_find(5));
+        assert_eq!([0, 0, 0, 0, 0, 0], representative);
+
+        let mut representative = [0u8, 2, 1, 9];
+        let uf = &mut UnionFind::<QuickUnionWith<Unweighted<true>, FullCompression>, u8, 4>::new(
+            &mut representative,
+        );
+        assert_eq!(
+            Err(UnionFindError::CorruptedParent { id: 2 }),
+            uf.try_find(2)
+        );
+        assert_eq!(
+            Err(UnionFindError::CorruptedParent { id: 3 }),
+            uf.try_find(3)
+        );
+    }
+
+    #[test]
+    fn test_compression_alias() {
+        let _: QuickUnionWith<ByRank, PathHalving> = QuickUnion::<ByRank, true>::default();
+        let _: QuickUnionWith<BySize, NoCompression> = QuickUnion::<BySize, false>::default();
+
+        let mut uf = UnionFind::<QuickUnion<ByRank, false>, u8, 10>::default();
+        uf.union_sets(4, 3);
+        uf.union_sets(3, 8);
+        uf.union_sets(9, 4);
+        assert!(uf.connected(8, 9));
+        assert_eq!([0, 1, 2, 4, 4, 5, 6, 7, 4, 4], uf.representative);
+
+        let mut uf = UnionFind::<QuickUnionWith<BySize, PathSplitting>, u8, 10>::default();
+        uf.union_sets(4, 3);
+        uf.union_sets(3, 8);
+        assert_eq!(3, uf.set_size(8));
+    }
+
     struct ByRankVec;
 
     impl AlgorithmContainer for QuickUnion<ByRankVec> {
         type HeuristicContainer<'a, const N: usize> = heapless::Vec<usize, N>;
         type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = heapless::Vec<R, N>;
+        type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+        type ComponentContainer = [usize; 0];
+        type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
     }
 
     impl<const N: usize> UnionFind<'_, QuickUnion<ByRankVec>, u8, N> {
@@ -430,19 +1148,24 @@ mod tests {
             Self {
                 representative,
                 heuristic,
+                siblings: [],
+                components: [0; 0],
+                history: (),
                 algorithm: Default::default(),
             }
         }
     }
 
     impl Heuristic for ByRankVec {
-        fn handle_decision<T>(
+        fn handle_decision<T, S>(
             a: T::IdentifierType,
             b: T::IdentifierType,
-            heuristic: &mut [usize],
+            heuristic: &mut [S],
             representative: &mut [T],
-        ) where
+        ) -> UnionResult<T::IdentifierType>
+        where
             T: VertexType,
+            S: HeuristicType,
         {
             ByRank::<false>::handle_decision(a, b, heuristic, representative)
         }
@@ -0,0 +1,374 @@
+//! Rem's algorithm implementation
+
+use crate::quickunion::{Compression, PathHalving};
+use crate::{
+    scan_set_size, AlgorithmContainer, Connected, Find, HeuristicType, SetSize, Union, UnionFind,
+    UnionFindError, UnionResult, Validate, VertexType,
+};
+
+/// [`Rem`] algorithm
+///
+/// Links by index so that the parent of a node is never larger than the node itself, which
+/// lets union walk both paths in lockstep and splice one onto the other on the way up.
+/// Only the representative buffer is needed, the root of every component is its smallest node
+///
+/// - `IS_SLICE` - boolean value, borrows the representative buffer instead of owning it
+#[derive(Debug, Default)]
+pub struct Rem<const IS_SLICE: bool = false>;
+
+impl AlgorithmContainer for Rem<false> {
+    type HeuristicContainer<'a, const N: usize> = [usize; 0];
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = [R; N];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
+}
+
+impl AlgorithmContainer for Rem<true> {
+    type HeuristicContainer<'a, const N: usize> = [usize; 0];
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = &'a mut [R];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> = ();
+}
+
+macro_rules! generate_default_ctor_rem {
+    ($($num_type:ident), *) => {
+        $(
+        impl<const N: usize> Default for UnionFind<'_, Rem, $num_type, N>
+        {
+            fn default() -> Self {
+                let mut representative = [0; N];
+
+                for i in 0..(N as $num_type) {
+                    representative[i as usize] = i;
+                }
+
+                Self {
+                    representative,
+                    heuristic: [0; 0],
+                    siblings: [],
+                    components: [0; 0],
+                    history: (),
+                    algorithm: Default::default(),
+                }
+            }
+        }
+        )*
+    };
+}
+
+impl<'a, T, const N: usize> UnionFind<'a, Rem<true>, T, N>
+where
+    T: VertexType,
+{
+    /// Every node in `representative` must have a parent that is not larger than itself
+    pub fn new(representative: &'a mut [T]) -> Self {
+        Self {
+            representative,
+            heuristic: [0; 0],
+            siblings: [],
+            components: [0; 0],
+            history: (),
+            algorithm: Default::default(),
+        }
+    }
+}
+
+/// Gets the parent of `a`, checking that `a` is in bounds and the parent does not exceed `a`
+#[inline(always)]
+fn try_parent<T>(representative: &[T], a: T::IdentifierType) -> Result<T, UnionFindError>
+where
+    T: VertexType,
+{
+    let parent = *representative
+        .get(T::usize(a))
+        .ok_or(UnionFindError::OutOfBounds {
+            id: T::usize(a),
+            len: representative.len(),
+        })?;
+
+    if T::usize(parent.id()) > T::usize(a) {
+        return Err(UnionFindError::CorruptedParent { id: T::usize(a) });
+    }
+
+    Ok(parent)
+}
+
+impl<T, const IS_SLICE: bool> Connected<T> for Rem<IS_SLICE>
+where
+    T: VertexType,
+{
+    /// Walks both paths in lockstep, always advancing the node with the larger parent.
+    /// Returns as soon as the paths meet or a root is passed by the other path
+    fn connected(
+        representative: &mut [T],
+        mut a: T::IdentifierType,
+        mut b: T::IdentifierType,
+    ) -> bool {
+        loop {
+            let (mut parent_a, mut parent_b) = (
+                representative[T::usize(a)].id(),
+                representative[T::usize(b)].id(),
+            );
+
+            if parent_a == parent_b {
+                return true;
+            }
+
+            if T::usize(parent_a) < T::usize(parent_b) {
+                core::mem::swap(&mut a, &mut b);
+                core::mem::swap(&mut parent_a, &mut parent_b);
+            }
+
+            // the root of `b` is at most `parent_b`, which is smaller than root `a`
+            if a == parent_a {
+                return false;
+            }
+            a = parent_a;
+        }
+    }
+
+    fn try_connected(
+        representative: &mut [T],
+        mut a: T::IdentifierType,
+        mut b: T::IdentifierType,
+    ) -> Result<bool, UnionFindError> {
+        loop {
+            let (mut parent_a, mut parent_b) = (
+                try_parent(representative, a)?.id(),
+                try_parent(representative, b)?.id(),
+            );
+
+            if parent_a == parent_b {
+                return Ok(true);
+            }
+
+            if T::usize(parent_a) < T::usize(parent_b) {
+                core::mem::swap(&mut a, &mut b);
+                core::mem::swap(&mut parent_a, &mut parent_b);
+            }
+
+            if a == parent_a {
+                return Ok(false);
+            }
+            a = parent_a;
+        }
+    }
+}
+
+impl<T, const IS_SLICE: bool> Union<T> for Rem<IS_SLICE>
+where
+    T: VertexType,
+{
+    fn union_sets<S: HeuristicType>(
+        representative: &mut [T],
+        _heuristic: &mut [S],
+        mut a: T::IdentifierType,
+        mut b: T::IdentifierType,
+    ) -> UnionResult<T::IdentifierType> {
+        loop {
+            let (mut parent_a, mut parent_b) = (
+                representative[T::usize(a)].id(),
+                representative[T::usize(b)].id(),
+            );
+
+            if parent_a == parent_b {
+                return UnionResult::AlreadyConnected {
+                    root: Self::find(representative, parent_a).id(),
+                };
+            }
+
+            if T::usize(parent_a) < T::usize(parent_b) {
+                core::mem::swap(&mut a, &mut b);
+                core::mem::swap(&mut parent_a, &mut parent_b);
+            }
+
+            // `a` either is a root that gets linked or gets spliced onto the path of `b`
+            representative[T::usize(a)] = representative[T::usize(b)];
+            if a == parent_a {
+                return UnionResult::Merged {
+                    root: Self::find(representative, a).id(),
+                    absorbed: a,
+                };
+            }
+            a = parent_a;
+        }
+    }
+
+    fn try_union_sets<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<UnionResult<T::IdentifierType>, UnionFindError> {
+        // splicing rewrites the paths on the way up, so both are validated before touching them
+        Self::try_find(representative, a)?;
+        Self::try_find(representative, b)?;
+        Ok(Self::union_sets(representative, heuristic, a, b))
+    }
+}
+
+impl<T, const IS_SLICE: bool> Find<T> for Rem<IS_SLICE>
+where
+    T: VertexType,
+{
+    fn find(representative: &mut [T], a: T::IdentifierType) -> T {
+        PathHalving::find(representative, a)
+    }
+
+    fn try_find(representative: &mut [T], mut a: T::IdentifierType) -> Result<T, UnionFindError> {
+        // parents never exceed their children, so the walk ends at a root
+        loop {
+            let parent = try_parent(representative, a)?;
+            if a == parent.id() {
+                return Ok(parent);
+            }
+
+            representative[T::usize(a)] = try_parent(representative, parent.id())?;
+            a = representative[T::usize(a)].id();
+        }
+    }
+}
+
+impl<T, const IS_SLICE: bool> SetSize<T> for Rem<IS_SLICE>
+where
+    T: VertexType,
+{
+    fn set_size<S: HeuristicType>(
+        representative: &mut [T],
+        _heuristic: &[S],
+        a: T::IdentifierType,
+    ) -> usize {
+        scan_set_size::<Self, T>(representative, a)
+    }
+}
+
+impl<T, const IS_SLICE: bool> Validate<T> for Rem<IS_SLICE>
+where
+    T: VertexType,
+{
+    /// Parents never exceed their children, which also rules out cycles
+    fn validate<S: HeuristicType>(
+        representative: &mut [T],
+        _heuristic: &mut [S],
+    ) -> Result<(), UnionFindError> {
+        match (0..representative.len()).find(|&i| T::usize(representative[i].id()) > i) {
+            Some(id) => Err(UnionFindError::CorruptedParent { id }),
+            None => Ok(()),
+        }
+    }
+}
+
+generate_default_ctor_rem!(u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use crate::{QuickUnion, Rem, UnionFind, UnionFindError, UnionResult};
+    use core::mem;
+
+    #[test]
+    fn test_rem() {
+        let mut uf = UnionFind::<Rem, u32, 10>::default();
+        uf.union_sets(4, 3);
+        uf.union_sets(3, 8);
+        uf.union_sets(6, 5);
+        uf.union_sets(9, 4);
+        assert!(uf.connected(3, 9));
+        assert!(uf.connected(8, 4));
+        assert!(!uf.connected(5, 9));
+        assert_eq!(3, uf.find(9));
+        assert_eq!(4, uf.set_size(8));
+    }
+
+    #[test]
+    fn test_rem_union_result() {
+        let mut uf = UnionFind::<Rem, u8, 10>::default();
+        assert_eq!(
+            UnionResult::Merged {
+                root: 3,
+                absorbed: 4
+            },
+            uf.union_sets(4, 3)
+        );
+        assert_eq!(
+            UnionResult::Merged {
+                root: 1,
+                absorbed: 3
+            },
+            uf.union_sets(1, 4)
+        );
+        assert_eq!(
+            UnionResult::AlreadyConnected { root: 1 },
+            uf.union_sets(3, 4)
+        );
+    }
+
+    #[test]
+    fn test_rem_slice() {
+        let mut representative = [0u8, 0, 1, 3, 3, 5];
+        let mut uf = UnionFind::<Rem<true>, u8, 6>::new(&mut representative);
+        assert!(uf.connected(2, 0));
+        assert!(!uf.connected(2, 4));
+        uf.union_sets(5, 2);
+        assert_eq!(0, uf.find(5));
+        assert_eq!(2, uf.count());
+    }
+
+    #[test]
+    fn test_rem_try() {
+        let mut uf = UnionFind::<Rem, u8, 4>::default();
+        assert_eq!(
+            Err(UnionFindError::OutOfBounds { id: 4, len: 4 }),
+            uf.try_union_sets(1, 4)
+        );
+        assert_eq!(
+            Err(UnionFindError::OutOfBounds { id: 7, len: 4 }),
+            uf.try_connected(7, 1)
+        );
+        assert_eq!(Ok(false), uf.try_connected(2, 1));
+
+        let mut representative = [0u8, 2, 2, 3];
+        let mut uf = UnionFind::<Rem<true>, u8, 4>::new(&mut representative);
+        assert_eq!(
+            Err(UnionFindError::CorruptedParent { id: 1 }),
+            uf.try_union_sets(1, 3)
+        );
+        assert_eq!([0, 2, 2, 3], representative);
+    }
+
+    #[test]
+    fn test_rem_matches_quickunion() {
+        let mut rem = UnionFind::<Rem, u16, 64>::default();
+        let mut qu = UnionFind::<QuickUnion, u16, 64>::default();
+        let mut seed = 0x2545_f491_u32;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            (seed % 64) as u16
+        };
+
+        for _ in 0..200 {
+            let (a, b) = (next(), next());
+            assert_eq!(
+                qu.union_sets(a, b).is_merged(),
+                rem.union_sets(a, b).is_merged()
+            );
+            let (a, b) = (next(), next());
+            assert_eq!(qu.connected(a, b), rem.connected(a, b));
+        }
+    }
+
+    #[test]
+    fn test_rem_sz() {
+        assert_eq!(
+            mem::size_of::<[u32; 10]>(),
+            mem::size_of::<UnionFind::<'_, Rem, u32, 10>>()
+        );
+        assert_eq!(
+            mem::size_of::<&'_ [u32]>(),
+            mem::size_of::<UnionFind::<'_, Rem<true>, u32, 10>>()
+        );
+    }
+}
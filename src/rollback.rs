@@ -0,0 +1,654 @@
+//! Rollback (undoable) union find implementation
+
+use core::marker::PhantomData;
+
+use crate::quickunion::{Heuristic, NoCompression};
+use crate::{
+    AlgorithmContainer, ByRank, BySize, Connected, Counted, Find, HeuristicType, Linked,
+    QuickUnionWith, SetSize, Union, UnionFind, UnionFindError, UnionResult, Validate, VertexType,
+};
+
+/// Everything a single union overwrites, see [`History`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undo<T> {
+    /// Absorbed root as it was before the union
+    absorbed: T,
+    /// Heuristic of the surviving root before the union
+    heuristic: usize,
+    /// Epoch of the history when this entry was pushed
+    epoch: usize,
+}
+
+/// Stack of the unions applied so far
+pub trait History<R> {
+    /// Whether unions are recorded at all, `false` skips the bookkeeping entirely
+    const RECORDS: bool;
+
+    fn len(&self) -> usize;
+    fn capacity(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of entries popped so far, every pop starts a new epoch
+    fn epoch(&self) -> usize;
+
+    /// Epoch in which the entry at position `i` was pushed, `None` past the top of the stack
+    fn epoch_at(&self, i: usize) -> Option<usize>;
+
+    /// Pushes `undo` in the current epoch, the caller must make sure that the stack is not full
+    fn push(&mut self, undo: Undo<R>);
+    fn pop(&mut self) -> Option<Undo<R>>;
+}
+
+impl<R> History<R> for () {
+    const RECORDS: bool = false;
+
+    fn len(&self) -> usize {
+        0
+    }
+
+    fn capacity(&self) -> usize {
+        0
+    }
+
+    fn epoch(&self) -> usize {
+        0
+    }
+
+    fn epoch_at(&self, _i: usize) -> Option<usize> {
+        None
+    }
+
+    fn push(&mut self, _undo: Undo<R>) {}
+
+    fn pop(&mut self) -> Option<Undo<R>> {
+        None
+    }
+}
+
+/// Fixed capacity [`History`] over an owned or borrowed buffer
+#[derive(Debug)]
+pub struct Stack<B> {
+    buffer: B,
+    len: usize,
+    epoch: usize,
+}
+
+impl<B> Stack<B> {
+    /// Creates an empty stack, the contents of `buffer` are ignored
+    pub fn new(buffer: B) -> Self {
+        Self {
+            buffer,
+            len: 0,
+            epoch: 0,
+        }
+    }
+}
+
+impl<R, const CAPACITY: usize> Default for Stack<[Option<Undo<R>>; CAPACITY]>
+where
+    R: Copy,
+{
+    fn default() -> Self {
+        Self::new([None; CAPACITY])
+    }
+}
+
+impl<R, B> History<R> for Stack<B>
+where
+    R: Copy,
+    B: AsRef<[Option<Undo<R>>]> + AsMut<[Option<Undo<R>>]>,
+{
+    const RECORDS: bool = true;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn capacity(&self) -> usize {
+        self.buffer.as_ref().len()
+    }
+
+    fn epoch(&self) -> usize {
+        self.epoch
+    }
+
+    fn epoch_at(&self, i: usize) -> Option<usize> {
+        let undo = self.buffer.as_ref()[..self.len].get(i)?;
+        undo.as_ref().map(|undo| undo.epoch)
+    }
+
+    fn push(&mut self, undo: Undo<R>) {
+        let epoch = self.epoch;
+        self.buffer.as_mut()[self.len] = Some(Undo { epoch, ..undo });
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<Undo<R>> {
+        self.len = self.len.checked_sub(1)?;
+        self.epoch += 1;
+        self.buffer.as_mut()[self.len].take()
+    }
+}
+
+/// [`Rollback`] algorithm
+///
+/// Union by rank or size without path compression, which keeps every union down to two
+/// overwritten entries that are pushed onto a history stack. Unions can then be undone one by
+/// one with [`UnionFind::undo`] or back to a [`Checkpoint`] with [`UnionFind::rollback_to`],
+/// finds stay `O(log N)`
+///
+/// This algorithm is parameterized by the following
+/// - `H` - Heuristic Type. Available types: [`ByRank`], [`BySize`]
+/// - `CAPACITY` - maximum number of unions kept in the history, 32 by default. Borrowed
+///   variants (`ByRank<true>`, `BySize<true>`) take a history slice of exactly `CAPACITY` entries
+///
+/// Once the history is full, [`UnionFind::union_sets`] panics on any union that would merge 2
+/// components, use [`UnionFind::try_union_sets`] to get [`UnionFindError::HistoryFull`] instead
+#[derive(Debug, Default)]
+pub struct Rollback<H = ByRank, const CAPACITY: usize = 32> {
+    heuristic: PhantomData<H>,
+}
+
+/// Algorithms that can undo their unions, finds must leave the representative buffer untouched
+pub trait Reversible: AlgorithmContainer {}
+
+impl<H, const CAPACITY: usize> Reversible for Rollback<H, CAPACITY> where Self: AlgorithmContainer {}
+impl<A> Reversible for Counted<A> where A: Reversible {}
+impl<A, const IS_SLICE: bool> Reversible for Linked<A, IS_SLICE>
+where
+    A: Reversible,
+    Self: AlgorithmContainer,
+{
+}
+
+impl<const CAPACITY: usize> AlgorithmContainer for Rollback<ByRank, CAPACITY> {
+    type HeuristicContainer<'a, const N: usize> = [usize; N];
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = [R; N];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> =
+        Stack<[Option<Undo<R>>; CAPACITY]>;
+}
+
+impl<const CAPACITY: usize> AlgorithmContainer for Rollback<BySize, CAPACITY> {
+    type HeuristicContainer<'a, const N: usize> = [usize; N];
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = [R; N];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> =
+        Stack<[Option<Undo<R>>; CAPACITY]>;
+}
+
+impl<const CAPACITY: usize> AlgorithmContainer for Rollback<ByRank<true>, CAPACITY> {
+    type HeuristicContainer<'a, const N: usize> = &'a mut [usize];
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = &'a mut [R];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> =
+        Stack<&'a mut [Option<Undo<R>>]>;
+}
+
+impl<const CAPACITY: usize> AlgorithmContainer for Rollback<BySize<true>, CAPACITY> {
+    type HeuristicContainer<'a, const N: usize> = &'a mut [usize];
+    type RepresentativeContainer<'a, R: VertexType + 'a, const N: usize> = &'a mut [R];
+    type SiblingContainer<'a, R: VertexType + 'a, const N: usize> = [R::IdentifierType; 0];
+    type ComponentContainer = [usize; 0];
+    type HistoryContainer<'a, R: VertexType + 'a, const N: usize> =
+        Stack<&'a mut [Option<Undo<R>>]>;
+}
+
+macro_rules! generate_default_ctor_rollback {
+    ($($num_type:ident), *) => {
+        $(
+        impl<const N: usize, const CAPACITY: usize> Default for UnionFind<'_, Rollback<ByRank, CAPACITY>, $num_type, N>
+        {
+            fn default() -> Self {
+                let mut representative = [0; N];
+
+                for i in 0..(N as $num_type) {
+                    representative[i as usize] = i;
+                }
+
+                Self {
+                    representative,
+                    heuristic: [0; N],
+                    siblings: [],
+                    components: [0; 0],
+                    history: Stack::new([None; CAPACITY]),
+                    algorithm: Default::default(),
+                }
+            }
+        }
+
+        impl<const N: usize, const CAPACITY: usize> Default for UnionFind<'_, Rollback<BySize, CAPACITY>, $num_type, N>
+        {
+            fn default() -> Self {
+                let mut representative = [0; N];
+
+                for i in 0..(N as $num_type) {
+                    representative[i as usize] = i;
+                }
+
+                Self {
+                    representative,
+                    heuristic: [1; N],
+                    siblings: [],
+                    components: [0; 0],
+                    history: Stack::new([None; CAPACITY]),
+                    algorithm: Default::default(),
+                }
+            }
+        }
+        )*
+    };
+}
+
+impl<'a, T, H, const N: usize, const CAPACITY: usize> UnionFind<'a, Rollback<H, CAPACITY>, T, N>
+where
+    T: VertexType + 'a,
+    Rollback<H, CAPACITY>: AlgorithmContainer<
+        HeuristicContainer<'a, N> = &'a mut [usize],
+        RepresentativeContainer<'a, T, N> = &'a mut [T],
+        SiblingContainer<'a, T, N> = [T::IdentifierType; 0],
+        ComponentContainer = [usize; 0],
+        HistoryContainer<'a, T, N> = Stack<&'a mut [Option<Undo<T>>]>,
+    >,
+{
+    /// Every union pushes one entry onto `history`, which caps the number of unions that can be
+    /// undone at once to `CAPACITY`
+    ///
+    /// # Panics
+    /// Panics if the length of `history` is not `CAPACITY`
+    pub fn new(
+        representative: &'a mut [T],
+        heuristic: &'a mut [usize],
+        history: &'a mut [Option<Undo<T>>],
+    ) -> Self {
+        assert!(
+            history.len() == CAPACITY,
+            "History slice must have len == CAPACITY!"
+        );
+        debug_assert!(
+            representative.len() >= N,
+            "Representative slice must have at least len >= N!"
+        );
+        debug_assert!(
+            heuristic.len() >= N,
+            "Heuristic slice must have at least len >= N!"
+        );
+
+        Self {
+            representative,
+            heuristic,
+            siblings: [],
+            components: [0; 0],
+            history: Stack::new(history),
+            algorithm: Default::default(),
+        }
+    }
+}
+
+/// Position in the history of a [`UnionFind`], see [`UnionFind::snapshot`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    len: usize,
+    /// Epoch of the history when the checkpoint was taken, tells apart the unions it saw from
+    /// the ones pushed in their place after an undo
+    epoch: usize,
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, A, T, N>
+where
+    T: VertexType + 'a,
+    A: AlgorithmContainer + Union<T>,
+{
+    /// Unions the roots `roots` and records what the union overwrites
+    pub(crate) fn union_recorded(
+        &mut self,
+        roots: [T; 2],
+    ) -> Result<UnionResult<T::IdentifierType>, UnionFindError> {
+        let history = &self.history;
+        if roots[0] != roots[1] && history.len() == history.capacity() {
+            return Err(UnionFindError::HistoryFull {
+                capacity: history.capacity(),
+            });
+        }
+
+        let heuristic = self.heuristic.as_ref();
+        let saved = roots.map(|root| {
+            let h = heuristic
+                .get(T::usize(root.id()))
+                .map_or(0, HeuristicType::get);
+            (root, h)
+        });
+
+        let result = A::try_union_sets(
+            self.representative.as_mut(),
+            self.heuristic.as_mut(),
+            roots[0].id(),
+            roots[1].id(),
+        )?;
+
+        if let UnionResult::Merged { root, .. } = result {
+            let [kept, absorbed] = if saved[0].0.id() == root {
+                saved
+            } else {
+                [saved[1], saved[0]]
+            };
+            self.history.push(Undo {
+                absorbed: absorbed.0,
+                heuristic: kept.1,
+                epoch: 0,
+            });
+        }
+
+        self.record(&result);
+        Ok(result)
+    }
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, A, T, N>
+where
+    T: VertexType + 'a,
+    A: Reversible,
+{
+    /// Current position in the history, pass it to [`UnionFind::rollback_to`] to undo every
+    /// union made after this call
+    pub fn snapshot(&self) -> Checkpoint {
+        Checkpoint {
+            len: self.history.len(),
+            epoch: self.history.epoch(),
+        }
+    }
+
+    /// Undoes the most recent union that merged 2 components and returns it,
+    /// or `None` if the history is empty
+    pub fn undo(&mut self) -> Option<UnionResult<T::IdentifierType>> {
+        let undo = self.history.pop()?;
+        let representative = self.representative.as_mut();
+        let absorbed = undo.absorbed.id();
+        // nothing compresses paths, so the absorbed root still points straight at the survivor
+        let root = representative[T::usize(absorbed)].id();
+
+        representative[T::usize(absorbed)] = undo.absorbed;
+        if let Some(heuristic) = self.heuristic.as_mut().get_mut(T::usize(root)) {
+            heuristic.set(undo.heuristic);
+        }
+
+        let result = UnionResult::Merged { root, absorbed };
+        self.unrecord(&result);
+        Some(result)
+    }
+
+    /// Undoes every union made since `checkpoint` was taken
+    ///
+    /// # Errors
+    /// Returns [`UnionFindError::StaleCheckpoint`] if a union made before `checkpoint` was taken
+    /// has been undone since, nothing is undone in that case
+    pub fn rollback_to(&mut self, checkpoint: Checkpoint) -> Result<(), UnionFindError> {
+        // the entry below the checkpoint is only replaced after being popped in a later epoch
+        let fresh = match checkpoint.len.checked_sub(1) {
+            Some(top) => self
+                .history
+                .epoch_at(top)
+                .is_some_and(|epoch| epoch <= checkpoint.epoch),
+            None => true,
+        };
+        if !fresh {
+            return Err(UnionFindError::StaleCheckpoint);
+        }
+
+        while self.history.len() > checkpoint.len {
+            self.undo();
+        }
+        Ok(())
+    }
+}
+
+impl<H, T, const CAPACITY: usize> Connected<T> for Rollback<H, CAPACITY>
+where
+    T: VertexType,
+    QuickUnionWith<H, NoCompression>: Connected<T>,
+{
+    fn connected(representative: &mut [T], a: T::IdentifierType, b: T::IdentifierType) -> bool {
+        QuickUnionWith::<H, NoCompression>::connected(representative, a, b)
+    }
+
+    fn try_connected(
+        representative: &mut [T],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<bool, UnionFindError> {
+        QuickUnionWith::<H, NoCompression>::try_connected(representative, a, b)
+    }
+}
+
+impl<H, T, const CAPACITY: usize> Union<T> for Rollback<H, CAPACITY>
+where
+    T: VertexType,
+    H: Heuristic,
+{
+    fn union_sets<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> UnionResult<T::IdentifierType> {
+        QuickUnionWith::<H, NoCompression>::union_sets(representative, heuristic, a, b)
+    }
+
+    fn try_union_sets<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> Result<UnionResult<T::IdentifierType>, UnionFindError> {
+        QuickUnionWith::<H, NoCompression>::try_union_sets(representative, heuristic, a, b)
+    }
+}
+
+impl<H, T, const CAPACITY: usize> Find<T> for Rollback<H, CAPACITY>
+where
+    T: VertexType,
+{
+    fn find(representative: &mut [T], a: T::IdentifierType) -> T {
+        QuickUnionWith::<H, NoCompression>::find(representative, a)
+    }
+
+    fn try_find(representative: &mut [T], a: T::IdentifierType) -> Result<T, UnionFindError> {
+        QuickUnionWith::<H, NoCompression>::try_find(representative, a)
+    }
+}
+
+impl<H, T, const CAPACITY: usize> SetSize<T> for Rollback<H, CAPACITY>
+where
+    T: VertexType,
+    QuickUnionWith<H, NoCompression>: SetSize<T>,
+{
+    fn set_size<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &[S],
+        a: T::IdentifierType,
+    ) -> usize {
+        QuickUnionWith::<H, NoCompression>::set_size(representative, heuristic, a)
+    }
+}
+
+impl<H, T, const CAPACITY: usize> Validate<T> for Rollback<H, CAPACITY>
+where
+    T: VertexType,
+    H: Heuristic,
+{
+    fn validate<S: HeuristicType>(
+        representative: &mut [T],
+        heuristic: &mut [S],
+    ) -> Result<(), UnionFindError> {
+        QuickUnionWith::<H, NoCompression>::validate(representative, heuristic)
+    }
+}
+
+generate_default_ctor_rollback!(u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use crate::{BySize, Counted, Linked, Rollback, UnionFind, UnionFindError, UnionResult};
+    use core::mem;
+
+    #[test]
+    fn test_rollback() {
+        let mut uf = UnionFind::<Rollback<BySize, 8>, u8, 8>::default();
+        uf.union_sets(0, 1);
+        let checkpoint = uf.snapshot();
+        uf.union_sets(2, 3);
+        uf.union_sets(1, 3);
+        uf.union_sets(0, 2);
+        assert!(uf.connected(0, 3));
+        assert_eq!(4, uf.set_size(2));
+
+        assert_eq!(
+            Some(UnionResult::Merged {
+                root: 0,
+                absorbed: 2
+            }),
+            uf.undo()
+        );
+        assert!(!uf.connected(0, 3));
+        assert_eq!(2, uf.set_size(3));
+
+        uf.union_sets(4, 5);
+        uf.rollback_to(checkpoint).unwrap();
+        assert!(uf.connected(0, 1));
+        assert!(!uf.connected(2, 3));
+        assert!(!uf.connected(4, 5));
+        assert_eq!([2, 1, 1, 1, 1, 1, 1, 1], *uf.heuristic());
+        assert_eq!(7, uf.count());
+
+        assert!(uf.undo().is_some());
+        assert_eq!(None, uf.undo());
+        assert_eq!(8, uf.count());
+    }
+
+    #[test]
+    fn test_rollback_full() {
+        let mut uf = UnionFind::<Rollback<BySize, 2>, u8, 4>::default();
+        uf.union_sets(0, 1);
+        uf.union_sets(2, 3);
+        assert_eq!(
+            Ok(UnionResult::AlreadyConnected { root: 0 }),
+            uf.try_union_sets(1, 0)
+        );
+        assert_eq!(
+            Err(UnionFindError::HistoryFull { capacity: 2 }),
+            uf.try_union_sets(1, 2)
+        );
+        uf.undo();
+        assert!(uf.try_union_sets(1, 2).is_ok());
+    }
+
+    #[test]
+    fn test_rollback_default_capacity() {
+        let mut uf = UnionFind::<Rollback, u16, 64>::default();
+        for i in 0..32 {
+            assert!(uf.union_sets(2 * i, 2 * i + 1).is_merged());
+        }
+        assert_eq!(
+            Err(UnionFindError::HistoryFull { capacity: 32 }),
+            uf.try_union_sets(0, 2)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rollback_full_panic() {
+        let mut uf = UnionFind::<Rollback<BySize, 1>, u8, 4>::default();
+        uf.union_sets(0, 1);
+        uf.union_sets(2, 3);
+    }
+
+    #[test]
+    fn test_rollback_slice() {
+        let mut representative = [0u8, 1, 2, 3, 4];
+        let mut heuristic = [0; 5];
+        let mut history = [None; 4];
+        let mut uf = UnionFind::<Rollback<crate::ByRank<true>, 4>, u8, 5>::new(
+            &mut representative,
+            &mut heuristic,
+            &mut history,
+        );
+        let checkpoint = uf.snapshot();
+        uf.union_sets(3, 4);
+        uf.union_sets(1, 4);
+        uf.union_sets(0, 2);
+        assert!(uf.connected(1, 3));
+        uf.rollback_to(checkpoint).unwrap();
+        assert_eq!(5, uf.count());
+        assert_eq!([0, 1, 2, 3, 4], representative);
+        assert_eq!([0; 5], heuristic);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rollback_slice_capacity() {
+        let mut representative = [0u8, 1, 2];
+        let mut heuristic = [1; 3];
+        let mut history = [None; 4];
+        UnionFind::<Rollback<crate::BySize<true>, 2>, u8, 3>::new(
+            &mut representative,
+            &mut heuristic,
+            &mut history,
+        );
+    }
+
+    #[test]
+    fn test_rollback_stale() {
+        let mut uf = UnionFind::<Rollback<BySize, 4>, u8, 6>::default();
+        uf.union_sets(0, 1);
+        let outer = uf.snapshot();
+        uf.union_sets(2, 3);
+        let inner = uf.snapshot();
+        uf.undo();
+        uf.union_sets(4, 5);
+
+        // `inner` saw 2 and 3 merged, that union is gone and another one took its place
+        assert_eq!(Err(UnionFindError::StaleCheckpoint), uf.rollback_to(inner));
+        assert!(uf.connected(4, 5));
+        assert_eq!(Ok(()), uf.rollback_to(outer));
+        assert!(uf.connected(0, 1));
+        assert!(!uf.connected(4, 5));
+        assert_eq!(Ok(()), uf.rollback_to(outer));
+        assert_eq!(Err(UnionFindError::StaleCheckpoint), uf.rollback_to(inner));
+
+        let empty = UnionFind::<Rollback<BySize, 4>, u8, 6>::default().snapshot();
+        assert_eq!(Ok(()), uf.rollback_to(empty));
+        assert_eq!(6, uf.count());
+    }
+
+    #[test]
+    fn test_rollback_wrapped() {
+        let mut uf = UnionFind::<Linked<Counted<Rollback<BySize, 4>>>, u8, 6>::default();
+        uf.union_sets(0, 1);
+        let checkpoint = uf.snapshot();
+        uf.union_sets(2, 3);
+        uf.union_sets(3, 1);
+        assert_eq!(4, uf.members(2).count());
+        assert_eq!(3, uf.count());
+        uf.rollback_to(checkpoint).unwrap();
+        assert_eq!(2, uf.members(1).count());
+        assert_eq!(1, uf.members(3).count());
+        assert_eq!(5, uf.count());
+    }
+
+    #[test]
+    fn test_rollback_sz() {
+        assert_eq!(
+            mem::size_of::<[u32; 10]>()
+                + mem::size_of::<[usize; 10]>()
+                + mem::size_of::<[Option<crate::rollback::Undo<u32>>; 4]>()
+                + mem::size_of::<usize>() * 2,
+            mem::size_of::<UnionFind::<'_, Rollback<BySize, 4>, u32, 10>>()
+        );
+    }
+}
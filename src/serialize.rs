@@ -0,0 +1,282 @@
+//! `serde` support
+//!
+//! [`UnionFind`] serializes as a struct of its representative and heuristic buffers, owned and
+//! borrowed alike. Deserialization is available for owned buffers, it validates the buffers
+//! with [`Validate`] and rebuilds everything derived from them, i.e. the component count of
+//! [`Counted`](crate::Counted) and the rings of [`Linked`](crate::Linked). The history of
+//! [`Rollback`](crate::Rollback) starts out empty
+
+use core::fmt;
+
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{AlgorithmContainer, Find, HeuristicBuffer, UnionFind, Validate, VertexType};
+
+const FIELDS: &[&str] = &["representative", "heuristic"];
+
+impl<'a, A, T, const N: usize> Serialize for UnionFind<'a, A, T, N>
+where
+    T: VertexType + Serialize,
+    A: AlgorithmContainer,
+    <A::HeuristicContainer<'a, N> as HeuristicBuffer>::Value: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("UnionFind", 2)?;
+        state.serialize_field("representative", self.representative.as_ref())?;
+        state.serialize_field("heuristic", self.heuristic.as_ref())?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Field {
+    Representative,
+    Heuristic,
+}
+
+/// Expected length of a buffer
+struct Length(usize);
+
+impl de::Expected for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of {} elements", self.0)
+    }
+}
+
+/// Deserializes a sequence into an existing buffer of exactly the same length
+struct Fill<'b, E>(&'b mut [E]);
+
+impl<'de, E> DeserializeSeed<'de> for Fill<'_, E>
+where
+    E: Deserialize<'de>,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, E> Visitor<'de> for Fill<'_, E>
+where
+    E: Deserialize<'de>,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        de::Expected::fmt(&Length(self.0.len()), f)
+    }
+
+    fn visit_seq<S>(self, mut seq: S) -> Result<(), S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        let len = self.0.len();
+        for (i, slot) in self.0.iter_mut().enumerate() {
+            *slot = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &Length(len)))?;
+        }
+
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(len + 1, &Length(len)));
+        }
+
+        Ok(())
+    }
+}
+
+/// Fills the buffers of a default [`UnionFind`]
+struct UnionFindVisitor<'a, A, T, const N: usize>(UnionFind<'a, A, T, N>)
+where
+    T: VertexType,
+    A: AlgorithmContainer;
+
+impl<'de, 'a, A, T, const N: usize> Visitor<'de> for UnionFindVisitor<'a, A, T, N>
+where
+    T: VertexType + Deserialize<'de>,
+    A: AlgorithmContainer + Validate<T> + Find<T>,
+    <A::HeuristicContainer<'a, N> as HeuristicBuffer>::Value: Deserialize<'de>,
+{
+    type Value = UnionFind<'a, A, T, N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("struct UnionFind")
+    }
+
+    fn visit_seq<S>(mut self, mut seq: S) -> Result<Self::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        seq.next_element_seed(Fill(self.0.representative.as_mut()))?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        seq.next_element_seed(Fill(self.0.heuristic.as_mut()))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        self.0.restore().map_err(de::Error::custom)?;
+        Ok(self.0)
+    }
+
+    fn visit_map<M>(mut self, mut map: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let (mut representative, mut heuristic) = (false, false);
+        while let Some(field) = map.next_key()? {
+            let (seen, buffer) = match field {
+                Field::Representative => {
+                    map.next_value_seed(Fill(self.0.representative.as_mut()))?;
+                    (&mut representative, "representative")
+                }
+                Field::Heuristic => {
+                    map.next_value_seed(Fill(self.0.heuristic.as_mut()))?;
+                    (&mut heuristic, "heuristic")
+                }
+            };
+
+            if *seen {
+                return Err(de::Error::duplicate_field(buffer));
+            }
+            *seen = true;
+        }
+
+        if !representative {
+            return Err(de::Error::missing_field("representative"));
+        }
+        if !heuristic {
+            return Err(de::Error::missing_field("heuristic"));
+        }
+
+        self.0.restore().map_err(de::Error::custom)?;
+        Ok(self.0)
+    }
+}
+
+impl<'de, 'a, A, T, const N: usize> Deserialize<'de> for UnionFind<'a, A, T, N>
+where
+    T: VertexType + Deserialize<'de>,
+    A: AlgorithmContainer + Validate<T> + Find<T>,
+    <A::HeuristicContainer<'a, N> as HeuristicBuffer>::Value: Deserialize<'de>,
+    Self: Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("UnionFind", FIELDS, UnionFindVisitor(Self::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::potential::PotentialVertex;
+    use crate::{
+        ByRank, BySize, Counted, Linked, Potential, QuickFind, QuickUnion, Rem, Rollback,
+        UnionFind, Unweighted,
+    };
+
+    fn roundtrip<U>(uf: &U) -> U
+    where
+        U: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        serde_json::from_str(&serde_json::to_string(uf).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let mut uf = UnionFind::<QuickUnion, u8, 6>::default();
+        uf.union_sets(0, 1);
+        uf.union_sets(2, 1);
+        uf.union_sets(4, 5);
+        assert_eq!(
+            r#"{"representative":[0,0,0,3,4,4],"heuristic":[1,0,0,0,1,0]}"#,
+            serde_json::to_string(&uf).unwrap()
+        );
+        let mut de = roundtrip(&uf);
+        assert!(de.connected(2, 0));
+        assert!(!de.connected(3, 4));
+
+        let mut uf = UnionFind::<QuickFind, u32, 4>::default();
+        uf.union_sets(3, 1);
+        assert!(roundtrip(&uf).connected(1, 3));
+
+        let mut uf = UnionFind::<Rem, u16, 4>::default();
+        uf.union_sets(3, 2);
+        assert!(roundtrip(&uf).connected(2, 3));
+
+        let mut uf = UnionFind::<Rollback<BySize, 4>, u8, 4>::default();
+        uf.union_sets(3, 2);
+        let mut de = roundtrip(&uf);
+        assert_eq!(2, de.set_size(2));
+        assert_eq!(None, de.undo());
+
+        let mut uf = UnionFind::<Potential, PotentialVertex<u8, i32>, 3>::default();
+        uf.union_with(0, 2, 7).unwrap();
+        assert_eq!(Some(-7), roundtrip(&uf).diff(2, 0));
+    }
+
+    #[test]
+    fn test_serde_rebuild() {
+        let mut uf = UnionFind::<Linked<Counted<QuickUnion<BySize>>>, u8, 5>::default();
+        uf.union_sets(0, 3);
+        uf.union_sets(4, 3);
+        let de = roundtrip(&uf);
+        assert_eq!(3, de.count());
+        let mut members = de.members(4).collect::<heapless::Vec<_, 5>>();
+        members.sort_unstable();
+        assert_eq!([0, 3, 4], members);
+    }
+
+    #[test]
+    fn test_serde_slice() {
+        let mut representative = [0u8, 0, 2];
+        let mut heuristic = [1, 0, 0];
+        let uf =
+            UnionFind::<QuickUnion<ByRank<true>>, u8, 3>::new(&mut representative, &mut heuristic);
+        let json = serde_json::to_string(&uf).unwrap();
+        let mut de = serde_json::from_str::<UnionFind<'_, QuickUnion, u8, 3>>(&json).unwrap();
+        assert!(de.connected(0, 1));
+    }
+
+    #[test]
+    fn test_serde_invalid() {
+        let de = |json| serde_json::from_str::<UnionFind<'_, QuickUnion, u8, 3>>(json).map(|_| ());
+        assert!(de(r#"{"representative":[0,0,2],"heuristic":[1,0,0]}"#).is_ok());
+        // wrong lengths, missing and duplicate fields
+        assert!(de(r#"{"representative":[0,0],"heuristic":[1,0,0]}"#).is_err());
+        assert!(de(r#"{"representative":[0,0,2,3],"heuristic":[1,0,0]}"#).is_err());
+        assert!(de(r#"{"representative":[0,0,2]}"#).is_err());
+        assert!(de(r#"{"heuristic":[0,0,0],"heuristic":[0,0,0]}"#).is_err());
+        // parent out of range, cycle, rank not increasing towards the root
+        assert!(de(r#"{"representative":[0,7,2],"heuristic":[1,0,0]}"#).is_err());
+        assert!(de(r#"{"representative":[1,2,0],"heuristic":[0,0,0]}"#).is_err());
+        assert!(de(r#"{"representative":[0,0,2],"heuristic":[0,0,0]}"#).is_err());
+        // ranks beyond `lg N`, the last one would overflow on the next union
+        assert!(de(r#"{"representative":[0,0,2],"heuristic":[2,0,0]}"#).is_err());
+        assert!(
+            de(r#"{"representative":[0,0,2],"heuristic":[1,0,18446744073709551615]}"#).is_err()
+        );
+
+        let de = |json| serde_json::from_str::<UnionFind<'_, QuickUnion<BySize>, u8, 3>>(json);
+        assert!(de(r#"{"representative":[0,0,2],"heuristic":[2,1,1]}"#).is_ok());
+        assert!(de(r#"{"representative":[0,0,2],"heuristic":[3,1,1]}"#).is_err());
+
+        let de = |json| serde_json::from_str::<UnionFind<'_, QuickFind, u8, 3>>(json);
+        assert!(de(r#"{"representative":[1,2,2],"heuristic":[]}"#).is_err());
+
+        let de = |json| serde_json::from_str::<UnionFind<'_, Rem, u8, 3>>(json);
+        assert!(de(r#"{"representative":[1,1,2],"heuristic":[]}"#).is_err());
+
+        let de = |json| serde_json::from_str::<UnionFind<'_, QuickUnion<Unweighted>, u8, 3>>(json);
+        assert!(de(r#"{"representative":[2,0,2],"heuristic":[]}"#).is_ok());
+    }
+}
@@ -0,0 +1,161 @@
+//! Interrupt-safe shared union find
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+use crate::{AlgorithmContainer, Connected, Find, Union, UnionFind, UnionResult, VertexType};
+
+/// [`UnionFind`] that can be placed in a `static` and shared between interrupt handlers and the
+/// main loop
+///
+/// Every operation takes `&self` and runs inside a [`critical_section`], so interrupts are
+/// masked for exactly one operation. The worst case of a single operation bounds the added
+/// interrupt latency
+///
+/// | Algorithm                            |   Union   |   Find    | Connected |
+/// | :----------------------------------- | --------: | --------: | --------: |
+/// | [`QuickFind`](crate::QuickFind)      |  `O(N)`   |  `O(1)`   |  `O(1)`   |
+/// | `QuickUnion<Unweighted, _>`          |  `O(N)`   |  `O(N)`   |  `O(N)`   |
+/// | `QuickUnion<ByRank \| BySize, _>`    | `O(lg N)` | `O(lg N)` | `O(lg N)` |
+/// | [`Rem`](crate::Rem)                  |  `O(N)`   |  `O(N)`   |  `O(N)`   |
+/// | [`Rollback`](crate::Rollback)        | `O(lg N)` | `O(lg N)` | `O(lg N)` |
+/// | [`Potential`](crate::Potential)      | `O(lg N)` | `O(lg N)` | `O(lg N)` |
+///
+/// Path compression only lowers the amortized cost, it does not change the worst case.
+/// [`Counted`](crate::Counted) and [`Linked`](crate::Linked) add `O(1)` to every union
+///
+/// ```
+/// use pulau_rs::{QuickUnion, SharedUnionFind, UnionFind};
+///
+/// static CONNECTIVITY: SharedUnionFind<'static, QuickUnion, u8, 16> = SharedUnionFind::new();
+///
+/// // initialize before enabling the interrupts that call `CONNECTIVITY.union_sets`
+/// CONNECTIVITY.init(UnionFind::<QuickUnion, u8, 16>::default());
+/// CONNECTIVITY.union_sets(3, 4);
+/// assert!(CONNECTIVITY.connected(4, 3));
+/// ```
+pub struct SharedUnionFind<'a, A, T, const N: usize>
+where
+    T: VertexType,
+    A: AlgorithmContainer,
+{
+    inner: Mutex<RefCell<Option<UnionFind<'a, A, T, N>>>>,
+}
+
+impl<'a, A, T, const N: usize> SharedUnionFind<'a, A, T, N>
+where
+    T: VertexType + 'a,
+    A: AlgorithmContainer,
+{
+    /// Creates an empty wrapper, [`SharedUnionFind::init`] must be called before any operation
+    pub const fn new() -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(None)),
+        }
+    }
+
+    /// Stores `uf`, replacing the previous one if any
+    pub fn init(&self, uf: UnionFind<'a, A, T, N>) {
+        critical_section::with(|cs| {
+            self.inner.borrow(cs).replace(Some(uf));
+        });
+    }
+
+    /// Runs `f` on the wrapped [`UnionFind`] inside a critical section
+    ///
+    /// # Panics
+    /// Panics if [`SharedUnionFind::init`] has not been called, or if `f` accesses `self` again
+    pub fn with<R>(&self, f: impl FnOnce(&mut UnionFind<'a, A, T, N>) -> R) -> R {
+        critical_section::with(|cs| {
+            let mut uf = self.inner.borrow_ref_mut(cs);
+            match uf.as_mut() {
+                Some(uf) => f(uf),
+                None => panic!("SharedUnionFind must be initialized before use!"),
+            }
+        })
+    }
+}
+
+impl<'a, A, T, const N: usize> Default for SharedUnionFind<'a, A, T, N>
+where
+    T: VertexType + 'a,
+    A: AlgorithmContainer,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, A, T, const N: usize> SharedUnionFind<'a, A, T, N>
+where
+    T: VertexType + 'a,
+    A: AlgorithmContainer + Union<T> + Find<T> + Connected<T>,
+{
+    /// See [`UnionFind::union_sets`]
+    pub fn union_sets(
+        &self,
+        a: T::IdentifierType,
+        b: T::IdentifierType,
+    ) -> UnionResult<T::IdentifierType> {
+        self.with(|uf| uf.union_sets(a, b))
+    }
+
+    /// See [`UnionFind::connected`]
+    pub fn connected(&self, a: T::IdentifierType, b: T::IdentifierType) -> bool {
+        self.with(|uf| uf.connected(a, b))
+    }
+
+    /// See [`UnionFind::find`]
+    pub fn find(&self, a: T::IdentifierType) -> T {
+        self.with(|uf| uf.find(a))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::SharedUnionFind;
+    use crate::{Counted, QuickFind, QuickUnion, UnionFind};
+
+    static SHARED: SharedUnionFind<'static, Counted<QuickUnion>, u16, 64> = SharedUnionFind::new();
+
+    #[test]
+    fn test_shared() {
+        SHARED.init(UnionFind::<Counted<QuickUnion>, u16, 64>::default());
+
+        std::thread::scope(|s| {
+            for t in 0..4 {
+                s.spawn(move || {
+                    for i in (t..63).step_by(4) {
+                        SHARED.union_sets(i, i + 1);
+                    }
+                });
+            }
+        });
+
+        assert!(SHARED.connected(0, 63));
+        assert_eq!(SHARED.find(0), SHARED.find(42));
+        assert_eq!(1, SHARED.with(|uf| uf.count()));
+    }
+
+    #[test]
+    fn test_shared_slice() {
+        let mut representative = [0u8, 1, 2, 3];
+        let shared = SharedUnionFind::<'_, QuickFind<true>, u8, 4>::default();
+        shared.init(UnionFind::<QuickFind<true>, u8, 4>::new(
+            &mut representative,
+        ));
+        shared.union_sets(1, 2);
+        assert!(shared.connected(2, 1));
+        assert!(!shared.connected(0, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_shared_uninit() {
+        let shared = SharedUnionFind::<'_, QuickFind, u8, 4>::new();
+        shared.connected(0, 1);
+    }
+}
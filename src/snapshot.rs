@@ -0,0 +1,574 @@
+//! Versioned binary snapshots
+//!
+//! [`UnionFind::encode_into`] writes a byte-stable, little-endian image of the buffers that
+//! can be stored in flash and loaded back with [`UnionFind::decode_from`]
+//!
+//! | Offset          | Size          | Content                                          |
+//! | :-------------- | :------------ | :----------------------------------------------- |
+//! | `0`             | `4`           | Magic `PULU`                                     |
+//! | `4`             | `1`           | Format version, currently `1`                    |
+//! | `5`             | `1`           | [`AlgorithmTag::TAG`] of the algorithm           |
+//! | `6`             | `1`           | [`Encode::WIDTH`] of the vertex type             |
+//! | `7`             | `8`           | `N`                                              |
+//! | `15`            | `N * WIDTH`   | Representative buffer                            |
+//! | `15 + N*WIDTH`  | `H * 8`       | Heuristic buffer, `H` is either `0` or `N`       |
+//! | end             | `4`           | CRC-32 of everything before it                   |
+//!
+//! Wrappers such as [`Counted`] and [`Linked`] share the tag of the algorithm they wrap,
+//! their bookkeeping is rebuilt when decoding. So is the compression of
+//! [`QuickUnionWith`], which does not change what the buffers mean
+
+use core::fmt;
+
+use crate::{
+    AlgorithmContainer, ByRank, BySize, Counted, Find, HeuristicType, Linked, Potential, QuickFind,
+    QuickUnionWith, Rem, Rollback, UnionFind, UnionFindError, Unweighted, Validate, VertexType,
+};
+
+const MAGIC: [u8; 4] = *b"PULU";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 15;
+const CHECKSUM_LEN: usize = 4;
+const HEURISTIC_WIDTH: u8 = 8;
+
+/// Fixed-width little-endian encoding of a vertex or heuristic value
+pub trait Encode: Sized {
+    /// Number of bytes taken by every value
+    const WIDTH: u8;
+
+    /// Writes `self` into `buf`, which is exactly [`Encode::WIDTH`] bytes long
+    fn encode(&self, buf: &mut [u8]);
+
+    /// Reads a value back from `buf`, which is exactly [`Encode::WIDTH`] bytes long,
+    /// or `None` if it does not fit into `Self`
+    fn decode(buf: &[u8]) -> Option<Self>;
+}
+
+macro_rules! generate_encode_impl {
+    ($($num_type:ident), *) => {
+        $(
+            impl Encode for $num_type {
+                const WIDTH: u8 = core::mem::size_of::<$num_type>() as u8;
+
+                #[inline(always)]
+                fn encode(&self, buf: &mut [u8]) {
+                    buf.copy_from_slice(&self.to_le_bytes());
+                }
+
+                #[inline(always)]
+                fn decode(buf: &[u8]) -> Option<Self> {
+                    buf.try_into().ok().map($num_type::from_le_bytes)
+                }
+            }
+        )*
+    };
+}
+
+generate_encode_impl!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+// pointer sized integers are always stored as 64 bits, so snapshots move between targets
+macro_rules! generate_encode_impl_widened {
+    ($($num_type:ident => $wide:ident), *) => {
+        $(
+            impl Encode for $num_type {
+                const WIDTH: u8 = 8;
+
+                #[inline(always)]
+                fn encode(&self, buf: &mut [u8]) {
+                    buf.copy_from_slice(&(*self as $wide).to_le_bytes());
+                }
+
+                #[inline(always)]
+                fn decode(buf: &[u8]) -> Option<Self> {
+                    let wide = buf.try_into().ok().map($wide::from_le_bytes)?;
+                    $num_type::try_from(wide).ok()
+                }
+            }
+        )*
+    };
+}
+
+generate_encode_impl_widened!(usize => u64, isize => i64);
+
+impl Encode for bool {
+    const WIDTH: u8 = 1;
+
+    #[inline(always)]
+    fn encode(&self, buf: &mut [u8]) {
+        buf[0] = u8::from(*self);
+    }
+
+    #[inline(always)]
+    fn decode(buf: &[u8]) -> Option<Self> {
+        match buf {
+            [0] => Some(false),
+            [1] => Some(true),
+            _ => None,
+        }
+    }
+}
+
+/// Identifies what the buffers of an algorithm mean in a snapshot
+pub trait AlgorithmTag {
+    const TAG: u8;
+}
+
+impl<const IS_SLICE: bool> AlgorithmTag for QuickFind<IS_SLICE> {
+    const TAG: u8 = 1;
+}
+
+impl<const IS_SLICE: bool, C> AlgorithmTag for QuickUnionWith<Unweighted<IS_SLICE>, C> {
+    const TAG: u8 = 2;
+}
+
+impl<const IS_SLICE: bool, C> AlgorithmTag for QuickUnionWith<ByRank<IS_SLICE>, C> {
+    const TAG: u8 = 3;
+}
+
+impl<const IS_SLICE: bool, C> AlgorithmTag for QuickUnionWith<BySize<IS_SLICE>, C> {
+    const TAG: u8 = 4;
+}
+
+impl<const IS_SLICE: bool> AlgorithmTag for Rem<IS_SLICE> {
+    const TAG: u8 = 5;
+}
+
+impl<const IS_SLICE: bool, const CAPACITY: usize> AlgorithmTag
+    for Rollback<ByRank<IS_SLICE>, CAPACITY>
+{
+    const TAG: u8 = 6;
+}
+
+impl<const IS_SLICE: bool, const CAPACITY: usize> AlgorithmTag
+    for Rollback<BySize<IS_SLICE>, CAPACITY>
+{
+    const TAG: u8 = 7;
+}
+
+impl<const IS_SLICE: bool> AlgorithmTag for Potential<ByRank<IS_SLICE>> {
+    const TAG: u8 = 8;
+}
+
+impl<const IS_SLICE: bool> AlgorithmTag for Potential<BySize<IS_SLICE>> {
+    const TAG: u8 = 9;
+}
+
+impl<A> AlgorithmTag for Counted<A>
+where
+    A: AlgorithmTag,
+{
+    const TAG: u8 = A::TAG;
+}
+
+impl<A, const IS_SLICE: bool> AlgorithmTag for Linked<A, IS_SLICE>
+where
+    A: AlgorithmTag,
+{
+    const TAG: u8 = A::TAG;
+}
+
+/// Errors returned by [`UnionFind::encode_into`] and [`UnionFind::decode_from`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The buffer holds `len` bytes but `needed` are required
+    BufferTooSmall { needed: usize, len: usize },
+    /// The buffer does not start with a snapshot
+    BadMagic,
+    /// The snapshot was written by an unknown format `version`
+    UnsupportedVersion { version: u8 },
+    /// The snapshot was written by another algorithm
+    AlgorithmMismatch { expected: u8, found: u8 },
+    /// The snapshot was written with another vertex type
+    WidthMismatch { expected: u8, found: u8 },
+    /// The snapshot holds `found` nodes instead of `expected`
+    LengthMismatch { expected: usize, found: u64 },
+    /// The checksum does not match the content
+    ChecksumMismatch,
+    /// The content is intact but does not form a valid union find
+    Invalid(UnionFindError),
+}
+
+impl From<UnionFindError> for SnapshotError {
+    fn from(err: UnionFindError) -> Self {
+        Self::Invalid(err)
+    }
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall { needed, len } => {
+                write!(f, "snapshot needs {} bytes but buffer has {}", needed, len)
+            }
+            Self::BadMagic => f.write_str("buffer does not contain a snapshot"),
+            Self::UnsupportedVersion { version } => {
+                write!(f, "snapshot version {} is not supported", version)
+            }
+            Self::AlgorithmMismatch { expected, found } => {
+                write!(f, "expected algorithm tag {} but found {}", expected, found)
+            }
+            Self::WidthMismatch { expected, found } => {
+                write!(f, "expected vertex width {} but found {}", expected, found)
+            }
+            Self::LengthMismatch { expected, found } => {
+                write!(f, "expected {} nodes but found {}", expected, found)
+            }
+            Self::ChecksumMismatch => f.write_str("snapshot checksum does not match"),
+            Self::Invalid(err) => write!(f, "snapshot is invalid: {}", err),
+        }
+    }
+}
+
+/// CRC-32 (IEEE), computed bitwise to avoid a lookup table
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+/// Total length of a snapshot
+fn encoded_len(nodes: usize, heuristic: usize, width: u8) -> usize {
+    HEADER_LEN
+        + nodes * usize::from(width)
+        + heuristic * usize::from(HEURISTIC_WIDTH)
+        + CHECKSUM_LEN
+}
+
+/// Splits a buffer into consecutive chunks
+struct Chunks<B> {
+    buf: B,
+    at: usize,
+}
+
+impl Chunks<&mut [u8]> {
+    fn next_mut(&mut self, len: u8) -> &mut [u8] {
+        let start = self.at;
+        self.at += usize::from(len);
+        &mut self.buf[start..self.at]
+    }
+}
+
+impl<'b> Chunks<&'b [u8]> {
+    fn next(&mut self, len: u8) -> &'b [u8] {
+        let start = self.at;
+        self.at += usize::from(len);
+        &self.buf[start..self.at]
+    }
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, A, T, N>
+where
+    T: VertexType + Encode,
+    A: AlgorithmContainer + AlgorithmTag,
+{
+    /// Number of bytes written by [`UnionFind::encode_into`]
+    pub fn encoded_len(&self) -> usize {
+        encoded_len(
+            self.representative.as_ref().len(),
+            self.heuristic.as_ref().len(),
+            T::WIDTH,
+        )
+    }
+
+    /// Writes a snapshot of the buffers to the front of `buf` and returns its length
+    ///
+    /// # Errors
+    /// Returns [`SnapshotError::BufferTooSmall`] if `buf` is shorter than
+    /// [`UnionFind::encoded_len`], nothing is written then
+    pub fn encode_into(&self, buf: &mut [u8]) -> Result<usize, SnapshotError> {
+        let len = self.encoded_len();
+        if buf.len() < len {
+            return Err(SnapshotError::BufferTooSmall {
+                needed: len,
+                len: buf.len(),
+            });
+        }
+
+        let representative = self.representative.as_ref();
+        let (body, checksum) = buf[..len].split_at_mut(len - CHECKSUM_LEN);
+        let mut chunks = Chunks { buf: body, at: 0 };
+        chunks.next_mut(4).copy_from_slice(&MAGIC);
+        chunks.next_mut(1)[0] = VERSION;
+        chunks.next_mut(1)[0] = A::TAG;
+        chunks.next_mut(1)[0] = T::WIDTH;
+        (representative.len() as u64).encode(chunks.next_mut(8));
+        for node in representative {
+            node.encode(chunks.next_mut(T::WIDTH));
+        }
+        for heuristic in self.heuristic.as_ref() {
+            heuristic.get().encode(chunks.next_mut(HEURISTIC_WIDTH));
+        }
+
+        checksum.copy_from_slice(&crc32(chunks.buf).to_le_bytes());
+        Ok(len)
+    }
+}
+
+impl<'a, A, T, const N: usize> UnionFind<'a, A, T, N>
+where
+    T: VertexType + Encode,
+    A: AlgorithmContainer + AlgorithmTag + Validate<T> + Find<T>,
+{
+    /// Loads a snapshot written by [`UnionFind::encode_into`] from the front of `buf`
+    ///
+    /// The buffers are validated like [`UnionFind::validate`] and the bookkeeping of wrappers is
+    /// rebuilt, the history of [`Rollback`] starts out empty
+    ///
+    /// # Errors
+    /// Returns [`SnapshotError`] if the snapshot was written for another algorithm, vertex type
+    /// or `N`, or if it is truncated or corrupted
+    pub fn decode_from(buf: &[u8]) -> Result<Self, SnapshotError>
+    where
+        Self: Default,
+    {
+        let mut uf = Self::default();
+        uf.load(buf)?;
+        Ok(uf)
+    }
+
+    /// Overwrites the buffers with a snapshot, they are left unspecified on error
+    fn load(&mut self, buf: &[u8]) -> Result<(), SnapshotError> {
+        if buf.len() < HEADER_LEN {
+            return Err(SnapshotError::BufferTooSmall {
+                needed: HEADER_LEN,
+                len: buf.len(),
+            });
+        }
+
+        let mut chunks = Chunks { buf, at: 0 };
+        if chunks.next(4) != MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+
+        let version = chunks.next(1)[0];
+        if version != VERSION {
+            return Err(SnapshotError::UnsupportedVersion { version });
+        }
+
+        let found = chunks.next(1)[0];
+        if found != A::TAG {
+            return Err(SnapshotError::AlgorithmMismatch {
+                expected: A::TAG,
+                found,
+            });
+        }
+
+        let found = chunks.next(1)[0];
+        if found != T::WIDTH {
+            return Err(SnapshotError::WidthMismatch {
+                expected: T::WIDTH,
+                found,
+            });
+        }
+
+        let representative = self.representative.as_mut();
+        let heuristic = self.heuristic.as_mut();
+        let nodes = u64::decode(chunks.next(8)).unwrap_or_default();
+        if nodes != representative.len() as u64 {
+            return Err(SnapshotError::LengthMismatch {
+                expected: representative.len(),
+                found: nodes,
+            });
+        }
+
+        let len = encoded_len(representative.len(), heuristic.len(), T::WIDTH);
+        if buf.len() < len {
+            return Err(SnapshotError::BufferTooSmall {
+                needed: len,
+                len: buf.len(),
+            });
+        }
+
+        let (body, checksum) = buf[..len].split_at(len - CHECKSUM_LEN);
+        if u32::decode(checksum) != Some(crc32(body)) {
+            return Err(SnapshotError::ChecksumMismatch);
+        }
+
+        for (id, node) in representative.iter_mut().enumerate() {
+            *node =
+                T::decode(chunks.next(T::WIDTH)).ok_or(UnionFindError::CorruptedParent { id })?;
+        }
+        for (id, rank) in heuristic.iter_mut().enumerate() {
+            rank.set(
+                usize::decode(chunks.next(HEURISTIC_WIDTH))
+                    .ok_or(UnionFindError::InconsistentHeuristic { id })?,
+            );
+        }
+
+        Ok(self.restore()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crc32, SnapshotError};
+    use crate::{
+        ByRank, BySize, Counted, Linked, Potential, PotentialVertex, QuickFind, QuickUnion, Rem,
+        Rollback, UnionFind, UnionFindError, Unweighted,
+    };
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(0xCBF4_3926, crc32(b"123456789"));
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let mut buf = [0u8; 128];
+
+        let mut uf = UnionFind::<QuickUnion, u16, 6>::default();
+        uf.union_sets(0, 1);
+        uf.union_sets(2, 1);
+        uf.union_sets(4, 5);
+        let len = uf.encode_into(&mut buf).unwrap();
+        assert_eq!(15 + 6 * 2 + 6 * 8 + 4, len);
+        assert_eq!(len, uf.encoded_len());
+        assert_eq!(b"PULU\x01\x03\x02\x06\0\0\0\0\0\0\0", &buf[..15]);
+        let mut de = UnionFind::<QuickUnion, u16, 6>::decode_from(&buf).unwrap();
+        assert_eq!(uf.representative(), de.representative());
+        assert_eq!(uf.heuristic(), de.heuristic());
+        assert!(de.connected(2, 0));
+
+        let mut uf = UnionFind::<QuickFind, usize, 4>::default();
+        uf.union_sets(3, 1);
+        let len = uf.encode_into(&mut buf).unwrap();
+        assert_eq!(15 + 4 * 8 + 4, len);
+        let mut de = UnionFind::<QuickFind, usize, 4>::decode_from(&buf[..len]).unwrap();
+        assert!(de.connected(1, 3));
+
+        let mut uf = UnionFind::<Rem, u8, 4>::default();
+        uf.union_sets(3, 2);
+        uf.encode_into(&mut buf).unwrap();
+        assert!(UnionFind::<Rem, u8, 4>::decode_from(&buf)
+            .unwrap()
+            .connected(2, 3));
+
+        let mut uf = UnionFind::<Rollback<BySize, 2>, u32, 4>::default();
+        uf.union_sets(3, 2);
+        uf.encode_into(&mut buf).unwrap();
+        let mut de = UnionFind::<Rollback<BySize, 2>, u32, 4>::decode_from(&buf).unwrap();
+        assert_eq!(2, de.set_size(2));
+        assert_eq!(None, de.undo());
+
+        let mut uf = UnionFind::<Potential, PotentialVertex<u8, bool>, 3>::default();
+        uf.union_with(0, 2, true).unwrap();
+        uf.encode_into(&mut buf).unwrap();
+        let mut de =
+            UnionFind::<Potential, PotentialVertex<u8, bool>, 3>::decode_from(&buf).unwrap();
+        assert_eq!(Some(true), de.diff(2, 0));
+    }
+
+    #[test]
+    fn test_snapshot_rebuild() {
+        let mut buf = [0u8; 64];
+        let mut representative = [0u8, 1, 2, 3, 4];
+        let mut heuristic = [1, 1, 1, 1, 1];
+        let mut uf =
+            UnionFind::<QuickUnion<BySize<true>>, u8, 5>::new(&mut representative, &mut heuristic);
+        uf.union_sets(0, 3);
+        uf.union_sets(4, 3);
+        uf.encode_into(&mut buf).unwrap();
+
+        // the tag ignores wrappers, compression and whether the buffers are borrowed
+        let de =
+            UnionFind::<Linked<Counted<QuickUnion<BySize>>>, u8, 5>::decode_from(&buf).unwrap();
+        assert_eq!(3, de.count());
+        let mut members = de.members(4).collect::<heapless::Vec<_, 5>>();
+        members.sort_unstable();
+        assert_eq!([0, 3, 4], members);
+    }
+
+    #[test]
+    fn test_snapshot_mismatch() {
+        let mut buf = [0u8; 64];
+        let uf = UnionFind::<QuickUnion, u8, 4>::default();
+        let len = uf.encode_into(&mut buf).unwrap();
+
+        assert_eq!(
+            Err(SnapshotError::BufferTooSmall {
+                needed: len,
+                len: 8
+            }),
+            uf.encode_into(&mut [0; 8])
+        );
+        assert_eq!(
+            Err(SnapshotError::AlgorithmMismatch {
+                expected: 4,
+                found: 3
+            }),
+            UnionFind::<QuickUnion<BySize>, u8, 4>::decode_from(&buf).map(|_| ())
+        );
+        assert_eq!(
+            Err(SnapshotError::AlgorithmMismatch {
+                expected: 6,
+                found: 3
+            }),
+            UnionFind::<Rollback<ByRank, 1>, u8, 4>::decode_from(&buf).map(|_| ())
+        );
+        assert_eq!(
+            Err(SnapshotError::WidthMismatch {
+                expected: 2,
+                found: 1
+            }),
+            UnionFind::<QuickUnion, u16, 4>::decode_from(&buf).map(|_| ())
+        );
+        assert_eq!(
+            Err(SnapshotError::LengthMismatch {
+                expected: 5,
+                found: 4
+            }),
+            UnionFind::<QuickUnion, u8, 5>::decode_from(&buf).map(|_| ())
+        );
+        assert_eq!(
+            Err(SnapshotError::BufferTooSmall {
+                needed: len,
+                len: len - 1
+            }),
+            UnionFind::<QuickUnion, u8, 4>::decode_from(&buf[..len - 1]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn test_snapshot_corrupted() {
+        let decode = |buf: &[u8]| UnionFind::<QuickUnion<Unweighted>, u8, 3>::decode_from(buf);
+        let mut buf = [0u8; 32];
+        let mut uf = UnionFind::<QuickUnion<Unweighted>, u8, 3>::default();
+        uf.union_sets(0, 1);
+        let len = uf.encode_into(&mut buf).unwrap();
+        assert!(decode(&buf).is_ok());
+
+        buf[0] = b'X';
+        assert_eq!(Err(SnapshotError::BadMagic), decode(&buf).map(|_| ()));
+        buf[0] = b'P';
+        buf[4] = 2;
+        assert_eq!(
+            Err(SnapshotError::UnsupportedVersion { version: 2 }),
+            decode(&buf).map(|_| ())
+        );
+        buf[4] = 1;
+
+        // flipped bit in the payload
+        buf[16] ^= 0x10;
+        assert_eq!(
+            Err(SnapshotError::ChecksumMismatch),
+            decode(&buf).map(|_| ())
+        );
+        buf[16] ^= 0x10;
+
+        // a cycle with a matching checksum is still rejected
+        buf[15..18].copy_from_slice(&[1, 2, 0]);
+        let checksum = crc32(&buf[..len - 4]);
+        buf[len - 4..len].copy_from_slice(&checksum.to_le_bytes());
+        assert_eq!(
+            Err(SnapshotError::Invalid(UnionFindError::CorruptedParent {
+                id: 1
+            })),
+            decode(&buf).map(|_| ())
+        );
+    }
+}